@@ -1,9 +1,10 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use app_state::AppState;
 use assets::Assets;
 use gpui::{actions, App, AppContext, KeyBinding, Menu, MenuItem};
+use story::StoryContainer;
 use ui::input::{Copy, Cut, Paste, Redo, Undo};
 
 mod app_state;
@@ -15,6 +16,14 @@ actions!(main_menu, [Quit]);
 fn init(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     story_workspace::init(app_state.clone(), cx);
 
+    let config_dir = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(".gpui-app");
+    workspace::persistence::init(config_dir, cx);
+    workspace::persistence::register_serializable_item::<StoryContainer>(cx);
+
     cx.bind_keys([KeyBinding::new("cmd-q", Quit, None)]);
 
     Ok(())
@@ -1,12 +1,11 @@
 use gpui::*;
 use prelude::FluentBuilder as _;
 use private::serde::Deserialize;
-use story::{
-    ButtonStory, CalendarStory, DropdownStory, IconStory, ImageStory, InputStory, ListStory,
-    PickerStory, PopupStory, ProgressStory, ResizableStory, ScrollableStory, StoryContainer,
-    SwitchStory, TableStory, TextStory, TooltipStory,
+use story::StoryContainer;
+use workspace::{
+    pane::{GoBack, GoForward},
+    ReopenClosedItem, TitleBar, Workspace, WorkspaceId,
 };
-use workspace::{TitleBar, Workspace};
 
 use std::sync::Arc;
 use ui::{
@@ -32,6 +31,12 @@ pub fn init(_app_state: Arc<AppState>, cx: &mut AppContext) {
     Theme::init(cx);
     ui::init(cx);
     story::init(cx);
+
+    cx.bind_keys([
+        KeyBinding::new("cmd-shift-t", ReopenClosedItem, Some("Workspace")),
+        KeyBinding::new("ctrl--", GoBack, Some("Pane")),
+        KeyBinding::new("ctrl-shift--", GoForward, Some("Pane")),
+    ]);
 }
 
 pub struct StoryWorkspace {
@@ -50,104 +55,42 @@ impl StoryWorkspace {
         })
         .detach();
 
-        StoryContainer::add_pane(
-            "Buttons",
-            "Displays a button or a component that looks like a button.",
-            ButtonStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Input",
-            "A control that allows the user to input text.",
-            InputStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Text",
-            "Links, paragraphs, checkboxes, and more.",
-            TextStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Switch",
-            "A control that allows the user to toggle between two states.",
-            SwitchStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Dropdowns",
-            "Displays a list of options for the user to pick from—triggered by a button.",
-            DropdownStory::new(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Picker",
-            "Picker is a component that allows the user to select an item from a list of options.",
-            PickerStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Popup",
-            "A popup displays content on top of the main page.",
-            PopupStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Tooltip",
-            "Displays a short message when users hover over an element.",
-            TooltipStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "List",
-            "A list displays a series of items.",
-            ListStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
+        let restore = workspace.update(cx, |workspace, cx| workspace.restore(cx));
+        let weak_workspace = workspace.downgrade();
+        cx.spawn(|_, mut cx| async move {
+            let restored = restore.await.unwrap_or_else(|err| {
+                log::error!("failed to restore workspace: {}", err);
+                false
+            });
+
+            if !restored {
+                if let Some(workspace) = weak_workspace.upgrade() {
+                    cx.update(|cx| Self::add_default_stories(workspace, cx))
+                        .ok();
+                }
+            }
+        })
         .detach();
 
-        StoryContainer::add_pane(
-            "Icon",
-            "Icon use examples",
-            IconStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
+        let locale_selector = cx.new_view(LocaleSelector::new);
+        Self {
+            workspace,
+            locale_selector,
+        }
+    }
 
-        StoryContainer::add_pane(
-            "Image",
-            "Render SVG image and Chart",
-            ImageStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
+    /// Add all the stories as the default layout, when there is no saved workspace.
+    fn add_default_stories(workspace: View<Workspace>, cx: &mut WindowContext) {
+        for story in story::STORIES {
+            StoryContainer::add_pane(
+                story.name,
+                story.description,
+                (story.view)(cx),
+                workspace.clone(),
+                cx,
+            )
+            .detach();
+        }
 
         // StoryContainer::add_panel(
         //     WebViewStory::view(cx).into(),
@@ -156,57 +99,6 @@ impl StoryWorkspace {
         //     px(450.),
         //     cx,
         // );
-
-        StoryContainer::add_pane(
-            "Table",
-            "Powerful table and datagrids built.",
-            TableStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Progress",
-            "Displays an indicator showing the completion progress of a task, typically displayed as a progress bar.",
-            ProgressStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Resizable",
-            "Accessible resizable panel groups and layouts with keyboard support.",
-            ResizableStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Scrollable",
-            "A scrollable area with scroll bar.",
-            ScrollableStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        StoryContainer::add_pane(
-            "Calendar",
-            "A calendar component.",
-            CalendarStory::view(cx).into(),
-            workspace.clone(),
-            cx,
-        )
-        .detach();
-
-        let locale_selector = cx.new_view(LocaleSelector::new);
-        Self {
-            workspace,
-            locale_selector,
-        }
     }
 
     pub fn new_local(
@@ -232,7 +124,7 @@ impl StoryWorkspace {
             };
 
             let window = cx.open_window(options, |cx| {
-                let workspace = cx.new_view(|cx| Workspace::new(None, cx));
+                let workspace = cx.new_view(|cx| Workspace::new(Some(WorkspaceId::from(1)), cx));
                 cx.new_view(|cx| Self::new(app_state.clone(), workspace, cx))
             })?;
 
@@ -1,6 +1,8 @@
+use std::time::Duration;
+
 use gpui::{
-    px, IntoElement, ParentElement, Render, SharedString, Styled, View, ViewContext, VisualContext,
-    WindowContext,
+    px, IntoElement, ParentElement, Render, SharedString, Styled, Timer, View, ViewContext,
+    VisualContext, WindowContext,
 };
 
 use ui::{
@@ -36,9 +38,54 @@ impl DropdownItem for Country {
     }
 }
 
+/// A language grouped by the `Recent` and `All` sections.
+#[derive(Clone)]
+struct Language {
+    name: SharedString,
+    recent: bool,
+    deprecated: bool,
+}
+
+impl Language {
+    fn new(name: impl Into<SharedString>, recent: bool) -> Self {
+        Self {
+            name: name.into(),
+            recent,
+            deprecated: false,
+        }
+    }
+
+    fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+}
+
+impl DropdownItem for Language {
+    type Value = SharedString;
+
+    fn title(&self) -> SharedString {
+        self.name.clone()
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.name
+    }
+
+    fn section(&self) -> Option<SharedString> {
+        Some(if self.recent { "Recent" } else { "All" }.into())
+    }
+
+    fn disabled(&self) -> bool {
+        self.deprecated
+    }
+}
+
 pub struct DropdownStory {
     country_dropdown: View<Dropdown<Vec<Country>>>,
     fruit_dropdown: View<Dropdown<SearchableVec<SharedString>>>,
+    tags_dropdown: View<Dropdown<SearchableVec<SharedString>>>,
+    language_dropdown: View<Dropdown<SearchableVec<Language>>>,
     simple_dropdown1: View<Dropdown<Vec<SharedString>>>,
     simple_dropdown2: View<Dropdown<Vec<SharedString>>>,
     simple_dropdown3: View<Dropdown<Vec<SharedString>>>,
@@ -78,15 +125,59 @@ impl DropdownStory {
                 .icon(IconName::Search)
                 .width(px(200.))
                 .menu_width(px(320.))
+                .creatable(true)
+        });
+
+        let tags = SearchableVec::new(vec![
+            "Bug".into(),
+            "Feature".into(),
+            "Documentation".into(),
+            "Performance".into(),
+            "Refactor".into(),
+            "Test".into(),
+        ]);
+        let tags_dropdown = cx.new_view(|cx| {
+            let mut dropdown = Dropdown::new("dropdown-tags", tags, None, cx)
+                .multiple(true)
+                .max_chips(2)
+                .cleanable(true)
+                .width(px(300.))
+                .placeholder("Filter by tags");
+            dropdown.set_selected_values(["Bug".into()], cx);
+            dropdown
+        });
+
+        let languages = SearchableVec::new(vec![
+            Language::new("Rust", true),
+            Language::new("TypeScript", true),
+            Language::new("C", false),
+            Language::new("C++", false),
+            Language::new("CoffeeScript", false).deprecated(),
+            Language::new("Go", false),
+            Language::new("Java", false),
+            Language::new("JavaScript", false),
+            Language::new("Python", false),
+            Language::new("Ruby", false),
+            Language::new("Swift", false),
+            Language::new("Zig", false),
+        ]);
+        let language_dropdown = cx.new_view(|cx| {
+            Dropdown::new("dropdown-languages", languages, None, cx)
+                .width(px(200.))
+                .placeholder("Language")
         });
 
         cx.new_view(|cx| {
             cx.subscribe(&country_dropdown, Self::on_dropdown_event)
                 .detach();
+            cx.subscribe(&fruit_dropdown, Self::on_fruit_dropdown_event)
+                .detach();
 
             Self {
                 country_dropdown,
                 fruit_dropdown,
+                tags_dropdown,
+                language_dropdown,
                 simple_dropdown1: cx.new_view(|cx| {
                     Dropdown::new(
                         "string-list1",
@@ -142,8 +233,32 @@ impl DropdownStory {
     ) {
         match event {
             DropdownEvent::Confirm(value) => println!("Selected country: {:?}", value),
+            DropdownEvent::ConfirmMultiple(values) => println!("Selected countries: {:?}", values),
+            DropdownEvent::Create(_) => {}
         }
     }
+
+    fn on_fruit_dropdown_event(
+        &mut self,
+        dropdown: View<Dropdown<SearchableVec<SharedString>>>,
+        event: &DropdownEvent<SearchableVec<SharedString>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let DropdownEvent::Create(fruit) = event else {
+            return;
+        };
+
+        // Simulate saving the new fruit to the server, then add and select it.
+        let fruit = SharedString::from(fruit.clone());
+        cx.spawn(|_, mut cx| async move {
+            Timer::after(Duration::from_millis(300)).await;
+            _ = dropdown.update(&mut cx, |dropdown, cx| {
+                dropdown.update_delegate(|fruits| fruits.push(fruit.clone()), cx);
+                dropdown.set_selected_value(&fruit, cx);
+            });
+        })
+        .detach();
+    }
 }
 
 impl Render for DropdownStory {
@@ -158,7 +273,9 @@ impl Render for DropdownStory {
                     .items_center()
                     .gap_4()
                     .child(self.country_dropdown.clone())
-                    .child(self.fruit_dropdown.clone()),
+                    .child(self.fruit_dropdown.clone())
+                    .child(self.tags_dropdown.clone())
+                    .child(self.language_dropdown.clone()),
             )
             .child(
                 v_flex()
@@ -178,6 +295,10 @@ impl Render for DropdownStory {
                         "fruit: {:?}",
                         self.fruit_dropdown.read(cx).selected_value()
                     ))
+                    .child(format!(
+                        "Tags: {:?}",
+                        self.tags_dropdown.read(cx).selected_values()
+                    ))
                     .child(format!(
                         "UI: {:?}",
                         self.simple_dropdown1.read(cx).selected_value()
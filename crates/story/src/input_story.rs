@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use gpui::{
     actions, div, px, AppContext, FocusHandle, InteractiveElement, IntoElement, KeyBinding,
     ParentElement as _, Render, SharedString, Styled, View, ViewContext, VisualContext,
@@ -8,14 +10,32 @@ use ui::{
     button::Button,
     checkbox::Checkbox,
     h_flex,
-    input::{InputEvent, OtpInput, TextInput},
+    input::{
+        AutoComplete, InputEvent, MaskPattern, NumberInput, NumberInputEvent, OtpCharset, OtpInput,
+        Suggestion, TagInput, TextInput,
+    },
     prelude::FluentBuilder as _,
     v_flex, Clickable, FocusableCycle, IconName, Selection, Sizable,
 };
 
 use crate::section;
 
-actions!(input_story, [Tab, TabPrev]);
+actions!(input_story, [Tab, TabPrev, WrapInQuotes]);
+
+const LANGUAGES: [(&str, &str); 12] = [
+    ("C", "1972"),
+    ("C++", "1985"),
+    ("C#", "2000"),
+    ("Go", "2009"),
+    ("Java", "1995"),
+    ("JavaScript", "1995"),
+    ("Kotlin", "2011"),
+    ("Python", "1991"),
+    ("Ruby", "1995"),
+    ("Rust", "2010"),
+    ("Swift", "2014"),
+    ("TypeScript", "2012"),
+];
 
 pub fn init(cx: &mut AppContext) {
     cx.bind_keys([
@@ -27,6 +47,9 @@ pub fn init(cx: &mut AppContext) {
 pub struct InputStory {
     input1: View<TextInput>,
     input2: View<TextInput>,
+    textarea: View<TextInput>,
+    find_input: View<TextInput>,
+    find_result: Option<(usize, usize)>,
     mash_input: View<TextInput>,
     disabled_input: View<TextInput>,
     prefix_input1: View<TextInput>,
@@ -34,6 +57,16 @@ pub struct InputStory {
     both_input1: View<TextInput>,
     large_input: View<TextInput>,
     small_input: View<TextInput>,
+    phone_input: View<TextInput>,
+    card_input: View<TextInput>,
+    currency_input: View<TextInput>,
+    number_input: View<NumberInput>,
+    number_value: Option<f64>,
+    integer_input: View<NumberInput<i64>>,
+    autocomplete: View<AutoComplete>,
+    email_input: View<TextInput>,
+    username_input: View<TextInput>,
+    tag_input: View<TagInput>,
     otp_masked: bool,
     otp_input: View<OtpInput>,
     otp_value: Option<SharedString>,
@@ -63,6 +96,33 @@ impl InputStory {
 
         cx.subscribe(&input2, Self::on_input_event).detach();
 
+        let textarea = cx.new_view(|cx| {
+            let mut input = TextInput::new(cx)
+                .multi_line()
+                .rows(4)
+                .placeholder("Enter text here...")
+                .context_menu(|menu, _| menu.menu("Wrap in quotes", Box::new(WrapInQuotes)));
+            input.set_text(
+                "Hello 世界，this is GPUI component.\n\nThe TextArea supports multi-line text, and the long text will be wrapped by the width of the input.",
+                cx,
+            );
+            input
+        });
+        cx.subscribe(&textarea, Self::on_input_event).detach();
+
+        let find_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .small()
+                .cleanable(true)
+                .placeholder("Find in textarea...")
+        });
+        cx.subscribe(&find_input, |this, _, ev: &InputEvent, cx| match ev {
+            InputEvent::Change(query) => this.find(query.clone(), cx),
+            InputEvent::PressEnter => this.find_next(true, cx),
+            _ => {}
+        })
+        .detach();
+
         let mask_input = cx.new_view(|cx| {
             let mut input = TextInput::new(cx).cleanable(true);
             input.set_masked(true, cx);
@@ -91,7 +151,121 @@ impl InputStory {
                 .placeholder("This input have prefix and suffix.")
         });
 
-        let otp_input = cx.new_view(|cx| OtpInput::new(6, cx).masked(true));
+        let phone_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .mask_pattern("(999) 999-9999")
+                .placeholder("(555) 123-4567")
+        });
+        let card_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .mask_pattern("9999 9999 9999 9999")
+                .placeholder("Card number")
+        });
+        let currency_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .mask_pattern(MaskPattern::number(Some(2)))
+                .prefix(|_| div().child("$").ml_3())
+                .placeholder("Amount")
+        });
+        cx.observe(&currency_input, |_, _, cx| cx.notify()).detach();
+
+        let number_input = cx.new_view(|cx| {
+            let mut input = NumberInput::new(cx).min(0.).max(100.).step(0.5);
+            input.set_placeholder("0 - 100, step 0.5", cx);
+            input
+        });
+        cx.subscribe(
+            &number_input,
+            |this, _, ev: &NumberInputEvent<f64>, cx| match ev {
+                NumberInputEvent::Change(value) => {
+                    this.number_value = *value;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+        let integer_input = cx.new_view(|cx| {
+            let mut input = NumberInput::new(cx).step(10);
+            input.set_value(Some(100), cx);
+            input
+        });
+
+        let autocomplete = cx.new_view(|cx| {
+            let autocomplete = AutoComplete::new(
+                |query, cx| {
+                    let query = query.to_lowercase();
+                    cx.background_executor().spawn(async move {
+                        LANGUAGES
+                            .iter()
+                            .filter(|(name, _)| name.to_lowercase().starts_with(&query))
+                            .map(|(name, year)| Suggestion::new(*name).description(*year))
+                            .collect()
+                    })
+                },
+                cx,
+            );
+            autocomplete.input().update(cx, |input, _| {
+                input.set_placeholder("Type a programming language...")
+            });
+            autocomplete
+        });
+
+        let email_input = cx.new_view(|cx| {
+            TextInput::new(cx).placeholder("Email").validation(|text| {
+                if text.is_empty() || text.contains('@') {
+                    Ok(())
+                } else {
+                    Err("Please enter a valid email address.".into())
+                }
+            })
+        });
+        cx.subscribe(&email_input, Self::on_input_event).detach();
+        let username_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .placeholder("Username, try `admin`")
+                .async_validation(|text, cx| {
+                    let text = text.to_string();
+                    cx.spawn(|cx| async move {
+                        // Simulate a request to check the username.
+                        cx.background_executor()
+                            .timer(Duration::from_millis(500))
+                            .await;
+                        if text == "admin" || text == "root" {
+                            Err(format!("The username `{}` is already taken.", text).into())
+                        } else {
+                            Ok(())
+                        }
+                    })
+                })
+        });
+
+        let tag_input = cx.new_view(|cx| {
+            let mut input = TagInput::new(cx)
+                .max(5)
+                .suggestions(LANGUAGES.iter().map(|(name, _)| SharedString::from(*name)));
+            input.input().update(cx, |input, _| {
+                input.set_placeholder("Add up to 5 languages...")
+            });
+            input.set_tags(["Rust".into()], cx);
+            input
+        });
+
+        let otp_input = cx.new_view(|cx| {
+            OtpInput::new(6, cx).masked(true).verify(|code, cx| {
+                let code = code.to_string();
+                cx.spawn(|cx| async move {
+                    // Simulate a request to verify the code.
+                    cx.background_executor()
+                        .timer(Duration::from_millis(500))
+                        .await;
+                    if code == "123456" {
+                        Ok(())
+                    } else {
+                        Err("The code is invalid, try `123456`.".into())
+                    }
+                })
+            })
+        });
         cx.subscribe(&otp_input, |this, _, ev: &InputEvent, cx| match ev {
             InputEvent::Change(text) => {
                 this.otp_value = Some(text.clone());
@@ -104,6 +278,9 @@ impl InputStory {
         Self {
             input1,
             input2,
+            textarea,
+            find_input,
+            find_result: None,
             mash_input: mask_input,
             disabled_input: cx.new_view(|cx| {
                 let mut input = TextInput::new(cx);
@@ -121,6 +298,16 @@ impl InputStory {
             prefix_input1,
             suffix_input1,
             both_input1,
+            phone_input,
+            card_input,
+            currency_input,
+            number_input,
+            number_value: None,
+            integer_input,
+            autocomplete,
+            email_input,
+            username_input,
+            tag_input,
             otp_masked: true,
             otp_input,
             otp_value: None,
@@ -134,6 +321,7 @@ impl InputStory {
             otp_input_large: cx.new_view(|cx| {
                 OtpInput::new(6, cx)
                     .groups(3)
+                    .charset(OtpCharset::Alphanumeric)
                     .large()
                     .default_value("012345")
                     .masked(true)
@@ -167,9 +355,56 @@ impl InputStory {
             InputEvent::PressEnter => println!("PressEnter"),
             InputEvent::Focus => println!("Focus"),
             InputEvent::Blur => println!("Blur"),
+            InputEvent::Validate(result) => println!("Validate: {:?}", result),
         };
     }
 
+    fn find(&mut self, query: SharedString, cx: &mut ViewContext<Self>) {
+        let query = query.to_string();
+        let count = self.textarea.update(cx, |textarea, cx| {
+            let text = textarea.text();
+            let highlights = if query.is_empty() {
+                vec![]
+            } else {
+                text.match_indices(query.as_str())
+                    .map(|(ix, _)| (ix..ix + query.len(), ui::yellow_200()))
+                    .collect()
+            };
+            textarea.set_highlights(highlights, cx);
+            textarea.highlights().len()
+        });
+
+        self.find_result = None;
+        if count > 0 {
+            self.find_next(true, cx);
+        }
+        cx.notify();
+    }
+
+    fn find_next(&mut self, forward: bool, cx: &mut ViewContext<Self>) {
+        let (ix, count) = self.textarea.update(cx, |textarea, cx| {
+            let ix = if forward {
+                textarea.select_next_highlight(cx)
+            } else {
+                textarea.select_prev_highlight(cx)
+            };
+            (ix, textarea.highlights().len())
+        });
+
+        self.find_result = ix.map(|ix| (ix, count));
+        cx.notify();
+    }
+
+    fn wrap_in_quotes(&mut self, cx: &mut ViewContext<Self>) {
+        self.textarea.update(cx, |textarea, cx| {
+            textarea.transact(cx, |textarea, cx| {
+                let len = textarea.text().len();
+                textarea.replace_text(len..len, "\"", cx);
+                textarea.replace_text(0..0, "\"", cx);
+            });
+        });
+    }
+
     fn toggle_opt_masked(&mut self, _: &Selection, cx: &mut ViewContext<Self>) {
         self.otp_masked = !self.otp_masked;
         self.otp_input
@@ -188,6 +423,7 @@ impl FocusableCycle for InputStory {
         [
             self.input1.focus_handle(cx),
             self.input2.focus_handle(cx),
+            self.textarea.focus_handle(cx),
             self.disabled_input.focus_handle(cx),
             self.mash_input.focus_handle(cx),
             self.prefix_input1.focus_handle(cx),
@@ -195,6 +431,15 @@ impl FocusableCycle for InputStory {
             self.suffix_input1.focus_handle(cx),
             self.large_input.focus_handle(cx),
             self.small_input.focus_handle(cx),
+            self.phone_input.focus_handle(cx),
+            self.card_input.focus_handle(cx),
+            self.currency_input.focus_handle(cx),
+            self.number_input.focus_handle(cx),
+            self.integer_input.focus_handle(cx),
+            self.autocomplete.focus_handle(cx),
+            self.email_input.focus_handle(cx),
+            self.username_input.focus_handle(cx),
+            self.tag_input.focus_handle(cx),
             self.otp_input.focus_handle(cx),
         ]
         .to_vec()
@@ -207,6 +452,7 @@ impl Render for InputStory {
             .key_context("InputStory")
             .on_action(cx.listener(Self::tab))
             .on_action(cx.listener(Self::tab_prev))
+            .on_action(cx.listener(|this, _: &WrapInQuotes, cx| this.wrap_in_quotes(cx)))
             .size_full()
             .p_4()
             .justify_start()
@@ -226,6 +472,43 @@ impl Render for InputStory {
                             .child(self.mash_input.clone()),
                     ),
             )
+            .child(
+                section("Textarea", cx)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .items_center()
+                            .child(self.find_input.clone())
+                            .child(div().flex_none().w_16().text_sm().child(
+                                match self.find_result {
+                                    Some((ix, count)) => format!("{}/{}", ix + 1, count),
+                                    None => "0/0".to_string(),
+                                },
+                            ))
+                            .child(
+                                Button::new("find-prev", cx)
+                                    .icon(IconName::ChevronUp)
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(|this, _, cx| this.find_next(false, cx))),
+                            )
+                            .child(
+                                Button::new("find-next", cx)
+                                    .icon(IconName::ChevronDown)
+                                    .ghost()
+                                    .small()
+                                    .on_click(cx.listener(|this, _, cx| this.find_next(true, cx))),
+                            ),
+                    )
+                    .child(self.textarea.clone())
+                    .child(
+                        Button::new("wrap-quotes", cx)
+                            .label("Wrap in quotes")
+                            .small()
+                            .tooltip("Two edits in a transaction, undo them as one step.")
+                            .on_click(cx.listener(|this, _, cx| this.wrap_in_quotes(cx))),
+                    ),
+            )
             .child(
                 h_flex()
                     .gap_3()
@@ -242,6 +525,29 @@ impl Render for InputStory {
                             .child(self.small_input.clone()),
                     ),
             )
+            .child(
+                section("Input Mask", cx)
+                    .child(self.phone_input.clone())
+                    .child(self.card_input.clone())
+                    .child(self.currency_input.clone())
+                    .child(format!(
+                        "Amount: {}",
+                        self.currency_input.read(cx).unmask_value()
+                    )),
+            )
+            .child(
+                section("Number Input", cx)
+                    .child(self.number_input.clone())
+                    .child(format!("Value: {:?}", self.number_value))
+                    .child(self.integer_input.clone()),
+            )
+            .child(section("Auto Complete", cx).child(self.autocomplete.clone()))
+            .child(
+                section("Validation", cx)
+                    .child(self.email_input.clone())
+                    .child(self.username_input.clone()),
+            )
+            .child(section("Tag Input", cx).child(self.tag_input.clone()))
             .child(
                 section(
                     h_flex()
@@ -37,7 +37,8 @@ pub use webview_story::WebViewStory;
 use gpui::{
     div, prelude::FluentBuilder as _, px, AnyElement, AnyView, AppContext, Div, EventEmitter,
     FocusableView, InteractiveElement, IntoElement, ParentElement, Pixels, Render, SharedString,
-    StatefulInteractiveElement, Styled as _, Task, View, ViewContext, VisualContext, WindowContext,
+    StatefulInteractiveElement, Styled as _, Task, View, ViewContext, VisualContext, WeakView,
+    WindowContext,
 };
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
@@ -45,13 +46,112 @@ use workspace::{
     Workspace, WorkspaceId,
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use ui::{divider::Divider, h_flex, label::Label, v_flex};
 
 pub fn init(cx: &mut AppContext) {
     input_story::init(cx);
 }
 
+/// A story of the gallery, the `name` is also used to restore it from the saved workspace.
+pub struct StoryEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub view: fn(&mut WindowContext) -> AnyView,
+}
+
+/// All the stories, in the order of the default layout.
+pub const STORIES: &[StoryEntry] = &[
+    StoryEntry {
+        name: "Buttons",
+        description: "Displays a button or a component that looks like a button.",
+        view: |cx| ButtonStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Input",
+        description: "A control that allows the user to input text.",
+        view: |cx| InputStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Text",
+        description: "Links, paragraphs, checkboxes, and more.",
+        view: |cx| TextStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Switch",
+        description: "A control that allows the user to toggle between two states.",
+        view: |cx| SwitchStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Dropdowns",
+        description: "Displays a list of options for the user to pick from—triggered by a button.",
+        view: |cx| DropdownStory::new(cx).into(),
+    },
+    StoryEntry {
+        name: "Picker",
+        description: "Picker is a component that allows the user to select an item from a list of options.",
+        view: |cx| PickerStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Popup",
+        description: "A popup displays content on top of the main page.",
+        view: |cx| PopupStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Tooltip",
+        description: "Displays a short message when users hover over an element.",
+        view: |cx| TooltipStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "List",
+        description: "A list displays a series of items.",
+        view: |cx| ListStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Icon",
+        description: "Icon use examples",
+        view: |cx| IconStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Image",
+        description: "Render SVG image and Chart",
+        view: |cx| ImageStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Table",
+        description: "Powerful table and datagrids built.",
+        view: |cx| TableStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Progress",
+        description: "Displays an indicator showing the completion progress of a task, typically displayed as a progress bar.",
+        view: |cx| ProgressStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Resizable",
+        description: "Accessible resizable panel groups and layouts with keyboard support.",
+        view: |cx| ResizableStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Scrollable",
+        description: "A scrollable area with scroll bar.",
+        view: |cx| ScrollableStory::view(cx).into(),
+    },
+    StoryEntry {
+        name: "Calendar",
+        description: "A calendar component.",
+        view: |cx| CalendarStory::view(cx).into(),
+    },
+];
+
+/// Create the story view by the name of the `StoryContainer`, used to restore the saved workspace.
+pub fn story_by_name(name: &str, cx: &mut WindowContext) -> Option<AnyView> {
+    STORIES
+        .iter()
+        .find(|story| story.name == name)
+        .map(|story| (story.view)(cx))
+}
+
 pub fn section(title: impl IntoElement, cx: &WindowContext) -> Div {
     use ui::theme::ActiveTheme;
     let theme = cx.theme();
@@ -126,6 +226,37 @@ impl Item for StoryContainer {
             ContainerEvent::Close => f(ItemEvent::CloseItem),
         }
     }
+
+    fn serialized_item_kind() -> Option<&'static str> {
+        Some("StoryContainer")
+    }
+
+    fn serialize(&self, _cx: &AppContext) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "name": self.name.as_ref(),
+            "description": self.description.as_ref(),
+        }))
+    }
+
+    fn deserialize(
+        _workspace: WeakView<Workspace>,
+        state: serde_json::Value,
+        cx: &mut WindowContext,
+    ) -> Task<Result<View<Self>>> {
+        let name = state["name"].as_str().unwrap_or_default().to_string();
+        let description = state["description"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let Some(story) = story_by_name(&name, cx) else {
+            return Task::ready(Err(anyhow!("Unknown story: {}", name)));
+        };
+
+        Task::ready(Ok(
+            cx.new_view(|cx| Self::new(name, description, cx).story(story))
+        ))
+    }
 }
 
 impl EventEmitter<ContainerEvent> for StoryContainer {}
@@ -3,7 +3,7 @@ use core::time;
 use fake::Fake;
 use gpui::{
     actions, div, px, ElementId, FocusHandle, FocusableView, InteractiveElement, IntoElement,
-    ParentElement, Render, RenderOnce, Styled, Timer, View, ViewContext, VisualContext,
+    ParentElement, Render, RenderOnce, Styled, Task, Timer, View, ViewContext, VisualContext,
     WindowContext,
 };
 
@@ -18,6 +18,11 @@ use ui::{
 
 actions!(list_story, [SelectedCompany]);
 
+/// The number of the companies to load in a page.
+const PAGE_SIZE: usize = 50;
+/// The total number of the companies on the simulated server.
+const TOTAL_COMPANIES: usize = 1_000;
+
 #[derive(Clone)]
 struct Company {
     name: String,
@@ -161,6 +166,24 @@ impl ListDelegate for CompanyListDelegate {
         }
     }
 
+    fn has_more(&self) -> bool {
+        self.companies.len() < TOTAL_COMPANIES
+    }
+
+    fn load_more(&mut self, cx: &mut ViewContext<List<Self>>) -> Task<()> {
+        cx.spawn(|list, mut cx| async move {
+            // Simulate loading the next page from the server.
+            Timer::after(time::Duration::from_millis(500)).await;
+
+            _ = list.update(&mut cx, |list, cx| {
+                let companies = &mut list.delegate_mut().companies;
+                let count = PAGE_SIZE.min(TOTAL_COMPANIES - companies.len());
+                companies.extend((0..count).map(|_| random_company()));
+                cx.notify();
+            });
+        })
+    }
+
     fn render_item(&self, ix: usize, _cx: &mut ViewContext<List<Self>>) -> Option<Self::Item> {
         let selected = ix == self.selected_index;
         if let Some(company) = self.companies.get(ix) {
@@ -189,7 +212,7 @@ impl ListStory {
     }
 
     fn new(cx: &mut ViewContext<Self>) -> Self {
-        let companies = (0..1_000)
+        let companies = (0..PAGE_SIZE)
             .map(|_| random_company())
             .collect::<Vec<Company>>();
 
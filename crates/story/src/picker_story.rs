@@ -1,15 +1,22 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use fake::Fake;
 use gpui::{
     deferred, div, prelude::FluentBuilder as _, px, Animation, AnimationExt as _, FocusHandle,
-    FocusableView, InteractiveElement as _, IntoElement, ParentElement, Render, Styled, Task,
-    Timer, View, ViewContext, VisualContext as _, WeakView, WindowContext,
+    FocusableView, InteractiveElement as _, IntoElement, ParentElement, Render, SharedString,
+    Styled, Task, View, ViewContext, VisualContext as _, WeakView, WindowContext,
 };
 
 use ui::{
     button::{Button, ButtonStyle},
+    fuzzy::{self, StringMatch},
     h_flex,
+    label::HighlightedLabel,
     list::{List, ListDelegate, ListItem},
     theme::ActiveTheme as _,
     v_flex, Clickable as _, Icon, IconName, StyledExt,
@@ -19,8 +26,9 @@ pub struct ListItemDeletegate {
     story: WeakView<PickerStory>,
     confirmed_index: Option<usize>,
     selected_index: Option<usize>,
-    items: Vec<Arc<String>>,
-    matches: Vec<Arc<String>>,
+    items: Arc<[SharedString]>,
+    matches: Vec<StringMatch>,
+    cancel_flag: Arc<AtomicBool>,
 }
 
 impl ListDelegate for ListItemDeletegate {
@@ -35,20 +43,25 @@ impl ListDelegate for ListItemDeletegate {
     }
 
     fn perform_search(&mut self, query: &str, cx: &mut ViewContext<List<Self>>) -> Task<()> {
-        let query = query.to_string();
+        // Stop the previous matching, and match on the background to keep typing smooth.
+        self.cancel_flag.store(true, Ordering::Relaxed);
+        self.cancel_flag = Arc::new(AtomicBool::new(false));
+        let cancel_flag = self.cancel_flag.clone();
+        let matches = fuzzy::match_strings_in_background(
+            self.items.clone(),
+            query,
+            cancel_flag.clone(),
+            cx.background_executor(),
+        );
+
         cx.spawn(move |this, mut cx| async move {
-            // Simulate a slow search.
-            let sleep = (0.05..0.1).fake();
-            Timer::after(Duration::from_secs_f64(sleep)).await;
+            let matches = matches.await;
+            if cancel_flag.load(Ordering::Relaxed) {
+                return;
+            }
 
             this.update(&mut cx, |this, cx| {
-                this.delegate_mut().matches = this
-                    .delegate()
-                    .items
-                    .iter()
-                    .filter(|item| item.to_lowercase().contains(&query.to_lowercase()))
-                    .cloned()
-                    .collect();
+                this.delegate_mut().matches = matches;
                 cx.notify();
             })
             .ok();
@@ -59,7 +72,8 @@ impl ListDelegate for ListItemDeletegate {
         let confirmed = Some(ix) == self.confirmed_index;
         let selected = Some(ix) == self.selected_index;
 
-        if let Some(item) = self.matches.get(ix) {
+        if let Some(m) = self.matches.get(ix) {
+            let item = self.items[m.candidate_id].clone();
             let list_item = ListItem::new(("item", ix))
                 .check_icon(ui::IconName::Check)
                 .confirmed(confirmed)
@@ -70,7 +84,7 @@ impl ListDelegate for ListItemDeletegate {
                     h_flex()
                         .items_center()
                         .justify_between()
-                        .child(item.to_string()),
+                        .child(HighlightedLabel::new(item, m.positions.clone())),
                 )
                 .suffix(|cx| {
                     Button::new("like", cx)
@@ -120,8 +134,8 @@ impl ListDelegate for ListItemDeletegate {
             cx.update_view(&story, |story, cx| {
                 if let Some(ix) = ix {
                     self.confirmed_index = Some(ix);
-                    if let Some(item) = self.matches.get(ix) {
-                        story.selected_value = Some(item.clone());
+                    if let Some(m) = self.matches.get(ix) {
+                        story.selected_value = Some(self.items[m.candidate_id].clone());
                     }
                 }
                 story.open = false;
@@ -142,7 +156,7 @@ impl ListDelegate for ListItemDeletegate {
 pub struct PickerStory {
     list: View<List<ListItemDeletegate>>,
     open: bool,
-    selected_value: Option<Arc<String>>,
+    selected_value: Option<SharedString>,
 }
 
 impl PickerStory {
@@ -151,7 +165,7 @@ impl PickerStory {
     }
 
     fn new(cx: &mut ViewContext<Self>) -> Self {
-        let items: Vec<Arc<String>> = [
+        let items: Arc<[SharedString]> = [
             "Baguette (France)",
             "Baklava (Turkey)",
             "Beef Wellington (UK)",
@@ -203,7 +217,7 @@ impl PickerStory {
             "Wiener Schnitzel (Austria)",
         ]
         .iter()
-        .map(|s| Arc::new(s.to_string()))
+        .map(|s| SharedString::from(*s))
         .collect();
 
         let story = cx.view().downgrade();
@@ -211,8 +225,9 @@ impl PickerStory {
             story,
             selected_index: None,
             confirmed_index: None,
-            items: items.clone(),
-            matches: items.clone(),
+            matches: fuzzy::match_strings(&items, "", &AtomicBool::new(false)),
+            items,
+            cancel_flag: Arc::new(AtomicBool::new(false)),
         };
         let list = cx.new_view(|cx| {
             let mut list = List::new(delegate, cx);
@@ -1,15 +1,20 @@
+use std::{fs::File, io::BufWriter, time::Duration};
+
 use fake::Fake;
 use gpui::{
-    div, img, IntoElement, ParentElement, Pixels, Render, SharedString, Styled, View, ViewContext,
-    VisualContext as _, WindowContext,
+    div, img, px, ClickEvent, IntoElement, ParentElement, Pixels, Render, SharedString, Styled,
+    Task, View, ViewContext, VisualContext as _, WindowContext,
 };
 use ui::{
+    button::Button,
     checkbox::Checkbox,
     h_flex,
     label::Label,
-    table::{ColSort, Table, TableDelegate, TableEvent},
+    table::{
+        CellValue, ColFilter, ColSort, FilterValue, Table, TableDelegate, TableEvent, TableFormat,
+    },
     theme::ActiveTheme as _,
-    v_flex, Icon, IconName, Selectable, Selection,
+    v_flex, Clickable as _, Icon, IconName, Selectable, Selection, Sizable as _,
 };
 
 struct Customer {
@@ -34,6 +39,29 @@ impl Customer {
         let avatar_url = format!("https://i.pravatar.cc/40?image={}", image_id);
         img(avatar_url).size_5().rounded_full()
     }
+
+    fn matches(&self, col_id: &str, filter: &FilterValue) -> bool {
+        let text: &str = match col_id {
+            "login" => &self.login,
+            "first_name" => &self.first_name,
+            "last_name" => &self.last_name,
+            "company" => &self.company,
+            "city" => &self.city,
+            "country" => &self.country,
+            "email" => &self.email,
+            _ => "",
+        };
+
+        match (col_id, filter) {
+            ("gender", FilterValue::Enum(checked)) => checked.contains(&self.gender),
+            ("age", FilterValue::Range(min, max)) => {
+                let age = self.age as f64;
+                min.map_or(true, |min| age >= min) && max.map_or(true, |max| age <= max)
+            }
+            (_, FilterValue::Text(query)) => text.to_lowercase().contains(&query.to_lowercase()),
+            _ => true,
+        }
+    }
 }
 
 fn randome_customers(size: usize) -> Vec<Customer> {
@@ -78,17 +106,24 @@ impl Column {
 
 struct CustomerTableDelegate {
     customers: Vec<Customer>,
+    /// The indexes of the customers that match the filters.
+    rows: Vec<usize>,
+    /// The active filters by the column id.
+    filters: Vec<(SharedString, FilterValue)>,
     columns: Vec<Column>,
     loop_selection: bool,
     col_resize: bool,
     col_order: bool,
     col_sort: bool,
+    fixed_cols: bool,
 }
 
 impl CustomerTableDelegate {
     fn new(size: usize) -> Self {
         Self {
             customers: randome_customers(size),
+            rows: (0..size).collect(),
+            filters: vec![],
             columns: vec![
                 Column::new("id", "ID", Some(ColSort::Ascending)),
                 Column::new("login", "Login", Some(ColSort::Default)),
@@ -109,8 +144,31 @@ impl CustomerTableDelegate {
             col_resize: true,
             col_order: true,
             col_sort: true,
+            fixed_cols: true,
         }
     }
+
+    fn customer(&self, row_ix: usize) -> Option<&Customer> {
+        self.customers.get(*self.rows.get(row_ix)?)
+    }
+
+    fn customer_mut(&mut self, row_ix: usize) -> Option<&mut Customer> {
+        self.customers.get_mut(*self.rows.get(row_ix)?)
+    }
+
+    fn apply_filters(&mut self) {
+        self.rows = self
+            .customers
+            .iter()
+            .enumerate()
+            .filter(|(_, customer)| {
+                self.filters
+                    .iter()
+                    .all(|(col_id, filter)| customer.matches(col_id, filter))
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+    }
 }
 
 impl TableDelegate for CustomerTableDelegate {
@@ -119,7 +177,7 @@ impl TableDelegate for CustomerTableDelegate {
     }
 
     fn rows_count(&self) -> usize {
-        self.customers.len()
+        self.rows.len()
     }
 
     fn col_name(&self, col_ix: usize) -> SharedString {
@@ -167,7 +225,7 @@ impl TableDelegate for CustomerTableDelegate {
         col_ix: usize,
         cx: &mut ViewContext<Table<Self>>,
     ) -> impl IntoElement {
-        let customer = self.customers.get(row_ix).unwrap();
+        let customer = self.customer(row_ix).unwrap();
 
         let col = self.columns.get(col_ix).unwrap();
         match col.id.as_ref() {
@@ -212,6 +270,124 @@ impl TableDelegate for CustomerTableDelegate {
         }
     }
 
+    fn fixed_left_cols_count(&self) -> usize {
+        if self.fixed_cols {
+            2
+        } else {
+            0
+        }
+    }
+
+    fn fixed_right_cols_count(&self) -> usize {
+        if self.fixed_cols {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn has_foot(&self) -> bool {
+        true
+    }
+
+    fn render_tf(&self, col_ix: usize, _: &mut ViewContext<Table<Self>>) -> impl IntoElement {
+        let col = self.columns.get(col_ix).unwrap();
+        let total = self.rows.len();
+        let customers = self.rows.iter().map(|ix| &self.customers[*ix]);
+
+        match col.id.as_ref() {
+            "id" => format!("{} rows", total),
+            "age" => format!(
+                "Avg: {}",
+                customers.map(|c| c.age).sum::<usize>() / total.max(1)
+            ),
+            "verified" => format!("{} verified", customers.filter(|c| c.verified).count()),
+            _ => String::new(),
+        }
+        .into_any_element()
+    }
+
+    fn cell_text(&self, row_ix: usize, col_ix: usize) -> SharedString {
+        let Some(customer) = self.customer(row_ix) else {
+            return SharedString::default();
+        };
+
+        match self.columns[col_ix].id.as_ref() {
+            "id" => customer.id.to_string(),
+            "login" => customer.login.clone(),
+            "first_name" => customer.first_name.clone(),
+            "last_name" => customer.last_name.clone(),
+            "company" => customer.company.clone(),
+            "city" => customer.city.clone(),
+            "country" => customer.country.clone(),
+            "email" => customer.email.clone(),
+            "phone" => customer.phone.clone(),
+            "gender" => match customer.gender {
+                0 => "Male".to_string(),
+                _ => "Famale".to_string(),
+            },
+            "age" => customer.age.to_string(),
+            "verified" => customer.verified.to_string(),
+            "confirmed" => customer.confirmed.to_string(),
+            _ => String::new(),
+        }
+        .into()
+    }
+
+    fn cell_value(&self, row_ix: usize, col_ix: usize) -> Option<CellValue> {
+        let customer = self.customer(row_ix)?;
+
+        match self.columns.get(col_ix)?.id.as_ref() {
+            "first_name" => Some(CellValue::Text(customer.first_name.clone().into())),
+            "last_name" => Some(CellValue::Text(customer.last_name.clone().into())),
+            "age" => Some(CellValue::Text(customer.age.to_string().into())),
+            "gender" => Some(CellValue::Select(
+                vec!["Male".into(), "Famale".into()],
+                Some(customer.gender),
+            )),
+            "verified" => Some(CellValue::Bool(customer.verified)),
+            _ => None,
+        }
+    }
+
+    fn commit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        value: CellValue,
+        _: &mut ViewContext<Table<Self>>,
+    ) -> Result<(), SharedString> {
+        let Some(col) = self.columns.get(col_ix) else {
+            return Ok(());
+        };
+        let Some(customer) = self.customer_mut(row_ix) else {
+            return Ok(());
+        };
+
+        match (col.id.as_ref(), value) {
+            ("first_name", CellValue::Text(text)) | ("last_name", CellValue::Text(text)) => {
+                if text.trim().is_empty() {
+                    return Err("Name can't be blank".into());
+                }
+
+                if col.id.as_ref() == "first_name" {
+                    customer.first_name = text.to_string();
+                } else {
+                    customer.last_name = text.to_string();
+                }
+            }
+            ("age", CellValue::Text(text)) => match text.trim().parse::<usize>() {
+                Ok(age) if (1..=150).contains(&age) => customer.age = age,
+                _ => return Err("Age must be a number in 1-150".into()),
+            },
+            ("gender", CellValue::Select(_, Some(ix))) => customer.gender = ix,
+            ("verified", CellValue::Bool(verified)) => customer.verified = verified,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     fn can_loop_select(&self) -> bool {
         self.loop_selection
     }
@@ -306,12 +482,186 @@ impl TableDelegate for CustomerTableDelegate {
                     col.sort = Some(ColSort::Default);
                 }
             }
+
+            self.apply_filters();
         }
     }
+
+    fn col_filter(&self, col_ix: usize) -> Option<ColFilter> {
+        match self.columns.get(col_ix)?.id.as_ref() {
+            "login" | "first_name" | "last_name" | "company" | "city" | "country" | "email" => {
+                Some(ColFilter::Text)
+            }
+            "gender" => Some(ColFilter::Enum(vec!["Male".into(), "Famale".into()])),
+            "age" => Some(ColFilter::Range),
+            _ => None,
+        }
+    }
+
+    fn perform_filter(
+        &mut self,
+        filters: &[(usize, FilterValue)],
+        _: &mut ViewContext<Table<Self>>,
+    ) {
+        self.filters = filters
+            .iter()
+            .filter_map(|(col_ix, filter)| {
+                Some((self.columns.get(*col_ix)?.id.clone(), filter.clone()))
+            })
+            .collect();
+        self.apply_filters();
+    }
+}
+
+struct Account {
+    id: usize,
+    name: String,
+    balance: f64,
+    depth: usize,
+    expanded: bool,
+}
+
+impl Account {
+    fn new(id: usize, name: String, depth: usize) -> Self {
+        Self {
+            id,
+            name,
+            balance: (0.0..100000.0).fake(),
+            depth,
+            expanded: false,
+        }
+    }
+
+    /// The accounts have 2 levels of sub-accounts.
+    fn has_children(&self) -> bool {
+        self.depth < 2
+    }
+}
+
+/// A tree table of the accounts, the sub-accounts are loaded lazily when expanding.
+struct AccountTableDelegate {
+    /// The visible accounts, the expanded sub-accounts follow their parent.
+    accounts: Vec<Account>,
+    next_id: usize,
+}
+
+impl AccountTableDelegate {
+    fn new(size: usize) -> Self {
+        Self {
+            accounts: (0..size)
+                .map(|id| Account::new(id, format!("Account {}", id + 1), 0))
+                .collect(),
+            next_id: size,
+        }
+    }
+
+    fn insert_sub_accounts(&mut self, id: usize) {
+        let Some(ix) = self.accounts.iter().position(|a| a.id == id) else {
+            return;
+        };
+
+        let parent = &self.accounts[ix];
+        let depth = parent.depth + 1;
+        let sub_accounts = (0..3)
+            .map(|i| {
+                Account::new(
+                    self.next_id + i,
+                    format!("{}-{}", parent.name, i + 1),
+                    depth,
+                )
+            })
+            .collect::<Vec<_>>();
+        self.next_id += sub_accounts.len();
+        self.accounts.splice(ix + 1..ix + 1, sub_accounts);
+    }
+}
+
+impl TableDelegate for AccountTableDelegate {
+    fn cols_count(&self) -> usize {
+        2
+    }
+
+    fn rows_count(&self) -> usize {
+        self.accounts.len()
+    }
+
+    fn col_name(&self, col_ix: usize) -> SharedString {
+        match col_ix {
+            0 => "Name".into(),
+            _ => "Balance".into(),
+        }
+    }
+
+    fn col_width(&self, col_ix: usize) -> Option<Pixels> {
+        match col_ix {
+            0 => Some(px(280.)),
+            _ => Some(px(120.)),
+        }
+    }
+
+    fn render_td(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        _: &mut ViewContext<Table<Self>>,
+    ) -> impl IntoElement {
+        let account = self.accounts.get(row_ix).unwrap();
+        match col_ix {
+            0 => account.name.clone(),
+            _ => format!("{:.2}", account.balance),
+        }
+    }
+
+    fn is_tree(&self) -> bool {
+        true
+    }
+
+    fn row_depth(&self, row_ix: usize) -> usize {
+        self.accounts[row_ix].depth
+    }
+
+    fn row_has_children(&self, row_ix: usize) -> bool {
+        self.accounts[row_ix].has_children()
+    }
+
+    fn row_expanded(&self, row_ix: usize) -> bool {
+        self.accounts[row_ix].expanded
+    }
+
+    fn perform_expand(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        cx: &mut ViewContext<Table<Self>>,
+    ) -> Option<Task<()>> {
+        let account = &mut self.accounts[row_ix];
+        account.expanded = expanded;
+        let (id, depth) = (account.id, account.depth);
+
+        if !expanded {
+            let end = self.accounts[row_ix + 1..]
+                .iter()
+                .position(|a| a.depth <= depth)
+                .map_or(self.accounts.len(), |ix| row_ix + 1 + ix);
+            self.accounts.drain(row_ix + 1..end);
+            return None;
+        }
+
+        // Simulate loading the sub-accounts from a remote server.
+        Some(cx.spawn(|table, mut cx| async move {
+            cx.background_executor()
+                .timer(Duration::from_millis(500))
+                .await;
+            _ = table.update(&mut cx, |table, _| {
+                table.delegate_mut().insert_sub_accounts(id);
+            });
+        }))
+    }
 }
 
 pub struct TableStory {
     table: View<Table<CustomerTableDelegate>>,
+    account_table: View<Table<AccountTableDelegate>>,
 }
 
 impl TableStory {
@@ -325,7 +675,12 @@ impl TableStory {
 
         cx.subscribe(&table, Self::on_table_event).detach();
 
-        Self { table }
+        let account_table = cx.new_view(|cx| Table::new(AccountTableDelegate::new(20), cx));
+
+        Self {
+            table,
+            account_table,
+        }
     }
 
     fn toggle_loop_selection(&mut self, s: &Selection, cx: &mut ViewContext<Self>) {
@@ -360,6 +715,33 @@ impl TableStory {
         });
     }
 
+    fn toggle_fixed_cols(&mut self, s: &Selection, cx: &mut ViewContext<Self>) {
+        let table = self.table.clone();
+        table.update(cx, |table, cx| {
+            table.delegate_mut().fixed_cols = s.is_selected();
+            cx.notify();
+        });
+    }
+
+    fn copy_as_csv(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+        self.table
+            .update(cx, |table, cx| table.copy_selection(TableFormat::Csv, cx));
+    }
+
+    fn export_csv(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+        let path = std::env::temp_dir().join("customers.csv");
+        let result = File::create(&path).and_then(|file| {
+            self.table
+                .read(cx)
+                .export(TableFormat::Csv, &mut BufWriter::new(file))
+        });
+
+        match result {
+            Ok(_) => println!("Exported to: {:?}", path),
+            Err(err) => println!("Failed to export: {}", err),
+        }
+    }
+
     fn on_table_event(
         &mut self,
         _: View<Table<CustomerTableDelegate>>,
@@ -372,6 +754,7 @@ impl TableStory {
             }
             TableEvent::SelectCol(ix) => println!("Select col: {}", ix),
             TableEvent::SelectRow(ix) => println!("Select row: {}", ix),
+            TableEvent::SelectRows(rows) => println!("Select rows: {:?}", rows),
         }
     }
 }
@@ -410,8 +793,32 @@ impl Render for TableStory {
                             .label("Column Sort")
                             .selected(delegate.col_sort)
                             .on_click(cx.listener(Self::toggle_col_sort)),
+                    )
+                    .child(
+                        Checkbox::new("fixed-cols")
+                            .label("Fixed Columns")
+                            .selected(delegate.fixed_cols)
+                            .on_click(cx.listener(Self::toggle_fixed_cols)),
+                    )
+                    .child(
+                        Button::new("copy-csv", cx)
+                            .label("Copy as CSV")
+                            .small()
+                            .on_click(cx.listener(Self::copy_as_csv)),
+                    )
+                    .child(
+                        Button::new("export-csv", cx)
+                            .label("Export CSV")
+                            .small()
+                            .on_click(cx.listener(Self::export_csv)),
                     ),
             )
             .child(self.table.clone())
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .h(px(320.))
+                    .child(self.account_table.clone()),
+            )
     }
 }
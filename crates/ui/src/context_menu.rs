@@ -215,12 +215,20 @@ impl Element for ContextMenu {
                         *position.borrow_mut() = event.position;
                         *open.borrow_mut() = true;
 
+                        let previous_focus = cx.focused();
                         let menu =
                             PopupMenu::build(cx, |menu, cx| (builder)(menu, cx)).into_element();
 
                         let open = open.clone();
-                        cx.subscribe(&menu, move |_, _: &DismissEvent, cx| {
+                        cx.subscribe(&menu, move |menu, _: &DismissEvent, cx| {
                             *open.borrow_mut() = false;
+                            // Restore the focus if the menu is dismissed by an item or the escape key,
+                            // then the focused element (e.g.: an input) can keep handling the keys.
+                            if menu.focus_handle(cx).contains_focused(cx) {
+                                if let Some(previous_focus) = &previous_focus {
+                                    previous_focus.focus(cx);
+                                }
+                            }
                             cx.refresh();
                         })
                         .detach();
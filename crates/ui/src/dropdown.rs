@@ -1,3 +1,8 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
 use gpui::{
     actions, deferred, div, prelude::FluentBuilder, px, rems, AnyElement, AppContext, ClickEvent,
     DismissEvent, Div, Element, ElementId, EventEmitter, FocusHandle, Focusable, FocusableView,
@@ -7,9 +12,11 @@ use gpui::{
 };
 
 use crate::{
+    fuzzy::{self, StringMatch},
     h_flex,
     input::ClearButton,
-    list::{self, List, ListDelegate, ListItem},
+    label::HighlightedLabel,
+    list::{self, List, ListDelegate, ListItem, ListRowKind},
     styled_ext::StyleSized,
     theme::ActiveTheme,
     Clickable, Icon, IconName, Sizable, Size, StyledExt,
@@ -29,9 +36,19 @@ pub fn init(cx: &mut AppContext) {
 
 /// A trait for items that can be displayed in a dropdown.
 pub trait DropdownItem {
-    type Value: Clone;
+    type Value: Clone + PartialEq;
     fn title(&self) -> SharedString;
     fn value(&self) -> &Self::Value;
+
+    /// Return the section title of the item, e.g.: `Recent`, the items in the same section should be adjacent.
+    fn section(&self) -> Option<SharedString> {
+        None
+    }
+
+    /// Return true if the item can't be selected.
+    fn disabled(&self) -> bool {
+        false
+    }
 }
 
 impl DropdownItem for String {
@@ -69,6 +86,23 @@ pub trait DropdownDelegate: Sized {
 
     fn get(&self, ix: usize) -> Option<&Self::Item>;
 
+    /// Return the section title of the item at `ix`, default is the `DropdownItem::section`.
+    ///
+    /// A header is shown before the first item of each section, and a separator between the sections.
+    fn section(&self, ix: usize) -> Option<SharedString> {
+        self.get(ix).and_then(|item| item.section())
+    }
+
+    /// Return true if the item at `ix` is disabled, default is the `DropdownItem::disabled`.
+    fn is_disabled(&self, ix: usize) -> bool {
+        self.get(ix).map_or(false, |item| item.disabled())
+    }
+
+    /// Return the byte offsets of the matched chars in the title of the item at `ix`, to highlight them.
+    fn match_positions(&self, _ix: usize) -> &[usize] {
+        &[]
+    }
+
     fn position<V>(&self, value: &V) -> Option<usize>
     where
         Self::Item: DropdownItem<Value = V>,
@@ -106,10 +140,79 @@ impl<T: DropdownItem> DropdownDelegate for Vec<T> {
     }
 }
 
+/// The row of the dropdown menu, the section headers and separators are inserted between the items.
+enum DropdownRow {
+    Header(SharedString),
+    Separator,
+    Item(usize),
+    /// The row to create a new item by the query, in the creatable dropdown.
+    Create(SharedString),
+}
+
 struct DropdownListDelegate<D: DropdownDelegate + 'static> {
     delegate: D,
     dropdown: WeakView<Dropdown<D>>,
+    rows: Vec<DropdownRow>,
+    /// The selected row index.
     selected_index: Option<usize>,
+    /// The search query to show the create row for, only set in the creatable dropdown.
+    create_query: Option<SharedString>,
+}
+
+impl<D> DropdownListDelegate<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    /// Rebuild the rows by the sections of the items, it must be called after the items changed.
+    fn update_rows(&mut self) {
+        self.rows.clear();
+
+        let mut last_section = None;
+        for ix in 0..self.delegate.len() {
+            let section = self.delegate.section(ix);
+            if section != last_section {
+                if let Some(title) = &section {
+                    if !self.rows.is_empty() {
+                        self.rows.push(DropdownRow::Separator);
+                    }
+                    self.rows.push(DropdownRow::Header(title.clone()));
+                }
+            }
+            last_section = section;
+            self.rows.push(DropdownRow::Item(ix));
+        }
+
+        // Show the create row at the end if there is no item with the same title as the query.
+        if let Some(query) = &self.create_query {
+            let query_lower = query.to_lowercase();
+            let exists = (0..self.delegate.len()).any(|ix| {
+                self.delegate
+                    .get(ix)
+                    .map_or(false, |item| item.title().to_lowercase() == query_lower)
+            });
+            if !exists {
+                if !self.rows.is_empty() {
+                    self.rows.push(DropdownRow::Separator);
+                }
+                self.rows.push(DropdownRow::Create(query.clone()));
+            }
+        }
+    }
+
+    /// Return the item index of the row.
+    fn item_index(&self, row_ix: usize) -> Option<usize> {
+        match self.rows.get(row_ix) {
+            Some(DropdownRow::Item(ix)) => Some(*ix),
+            _ => None,
+        }
+    }
+
+    /// Return the row index of the item.
+    fn row_index(&self, item_ix: usize) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| matches!(row, DropdownRow::Item(ix) if *ix == item_ix))
+    }
 }
 
 impl<D> ListDelegate for DropdownListDelegate<D>
@@ -119,7 +222,20 @@ where
     type Item = ListItem;
 
     fn items_count(&self) -> usize {
-        self.delegate.len()
+        self.rows.len()
+    }
+
+    fn row_kind(&self, ix: usize) -> ListRowKind {
+        match self.rows.get(ix) {
+            Some(DropdownRow::Header(_)) => ListRowKind::Header,
+            Some(DropdownRow::Separator) => ListRowKind::Separator,
+            _ => ListRowKind::Item,
+        }
+    }
+
+    fn is_item_disabled(&self, ix: usize) -> bool {
+        self.item_index(ix)
+            .map_or(false, |ix| self.delegate.is_disabled(ix))
     }
 
     fn confirmed_index(&self) -> Option<usize> {
@@ -135,14 +251,80 @@ where
             .upgrade()
             .map_or(Size::Medium, |dropdown| dropdown.read(cx).size);
 
-        if let Some(item) = self.delegate.get(ix) {
+        // The headers and separators use the same `ListItem` as the items, to keep the rows in the same height.
+        let item_ix = match self.rows.get(ix)? {
+            DropdownRow::Header(title) => {
+                return Some(
+                    ListItem::new(("list-header", ix))
+                        .disabled(true)
+                        .input_text_size(size)
+                        .list_size(size)
+                        .child(div().whitespace_nowrap().child(title.clone())),
+                )
+            }
+            DropdownRow::Separator => {
+                return Some(
+                    ListItem::new(("list-separator", ix))
+                        .disabled(true)
+                        .input_text_size(size)
+                        .list_size(size)
+                        .child(
+                            // The space keeps the line height as the items.
+                            div().relative().w_full().child(" ").child(
+                                div()
+                                    .absolute()
+                                    .top_1_2()
+                                    .left_0()
+                                    .right_0()
+                                    .h(px(1.))
+                                    .bg(cx.theme().border),
+                            ),
+                        ),
+                );
+            }
+            DropdownRow::Create(query) => {
+                return Some(
+                    ListItem::new(("list-create", ix))
+                        .selected(selected)
+                        .cursor_pointer()
+                        .input_text_size(size)
+                        .list_size(size)
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .whitespace_nowrap()
+                                .child(Icon::new(IconName::Plus).small())
+                                .child(format!("Create \"{}\"", query)),
+                        ),
+                );
+            }
+            DropdownRow::Item(item_ix) => *item_ix,
+        };
+
+        if let Some(item) = self.delegate.get(item_ix) {
+            let (multiple, checked) = self.dropdown.upgrade().map_or((false, false), |dropdown| {
+                let dropdown = dropdown.read(cx);
+                (dropdown.multiple, dropdown.is_selected(item.value()))
+            });
+            let disabled = self.delegate.is_disabled(item_ix);
+
             let list_item = ListItem::new(("list-item", ix))
                 .check_icon(IconName::Check)
-                .cursor_pointer()
-                .selected(selected)
+                .disabled(disabled)
+                .when(!disabled, |this| this.cursor_pointer())
+                .map(|this| {
+                    if multiple {
+                        this.confirmed(checked)
+                    } else {
+                        this.selected(selected)
+                    }
+                })
                 .input_text_size(size)
                 .list_size(size)
-                .child(div().whitespace_nowrap().child(item.title().to_string()));
+                .child(HighlightedLabel::new(
+                    item.title(),
+                    self.delegate.match_positions(item_ix),
+                ));
             Some(list_item)
         } else {
             None
@@ -159,12 +341,39 @@ where
     }
 
     fn confirm(&mut self, ix: Option<usize>, cx: &mut ViewContext<List<Self>>) {
+        if let Some(DropdownRow::Create(query)) = ix.and_then(|ix| self.rows.get(ix)) {
+            let query = query.to_string();
+            if let Some(view) = self.dropdown.upgrade() {
+                cx.update_view(&view, |view, cx| {
+                    cx.emit(DropdownEvent::Create(query));
+                    if !view.multiple {
+                        view.focus(cx);
+                        view.open = false;
+                    }
+                    cx.notify();
+                });
+            }
+            return;
+        }
+
         self.selected_index = ix;
 
         if let Some(view) = self.dropdown.upgrade() {
             cx.update_view(&view, |view, cx| {
+                // Toggle the item and keep the menu open in the multiple mode.
+                if view.multiple {
+                    if let Some(item) = ix
+                        .and_then(|ix| self.item_index(ix))
+                        .and_then(|ix| self.delegate.get(ix))
+                    {
+                        view.toggle_value(item.value().clone(), item.title(), cx);
+                    }
+                    return;
+                }
+
                 let selected_value = self
                     .selected_index
+                    .and_then(|ix| self.item_index(ix))
                     .and_then(|ix| self.delegate.get(ix))
                     .map(|item| item.value().clone());
                 cx.emit(DropdownEvent::Confirm(selected_value.clone()));
@@ -176,11 +385,25 @@ where
     }
 
     fn perform_search(&mut self, query: &str, cx: &mut ViewContext<List<Self>>) -> Task<()> {
-        self.dropdown
-            .upgrade()
-            .map_or(Task::Ready(None), |dropdown| {
-                dropdown.update(cx, |_, cx| self.delegate.perform_search(query, cx))
-            })
+        let Some(dropdown) = self.dropdown.upgrade() else {
+            return Task::Ready(None);
+        };
+
+        let (search, creatable) = dropdown.update(cx, |dropdown, cx| {
+            (self.delegate.perform_search(query, cx), dropdown.creatable)
+        });
+        let create_query: Option<SharedString> =
+            (creatable && !query.is_empty()).then(|| query.to_string().into());
+
+        // Rebuild the rows after searching, to keep the matched items grouped by the sections.
+        cx.spawn(|list, mut cx| async move {
+            search.await;
+            _ = list.update(&mut cx, |list, cx| {
+                list.delegate_mut().create_query = create_query;
+                list.delegate_mut().update_rows();
+                cx.notify();
+            });
+        })
     }
 
     fn set_selected_index(&mut self, ix: Option<usize>, _: &mut ViewContext<List<Self>>) {
@@ -207,6 +430,12 @@ where
 
 pub enum DropdownEvent<D: DropdownDelegate + 'static> {
     Confirm(Option<<D::Item as DropdownItem>::Value>),
+    /// The selected values have changed in the multiple mode.
+    ConfirmMultiple(Vec<<D::Item as DropdownItem>::Value>),
+    /// The create row was confirmed with the search query, in the creatable dropdown.
+    ///
+    /// The app should add the item, e.g.: by [`Dropdown::update_delegate`], then select it.
+    Create(String),
 }
 
 pub struct Dropdown<D: DropdownDelegate + 'static> {
@@ -220,6 +449,11 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
     placeholder: SharedString,
     title_prefix: Option<SharedString>,
     selected_value: Option<<D::Item as DropdownItem>::Value>,
+    multiple: bool,
+    /// The selected values with the titles in the multiple mode.
+    selected_values: Vec<(<D::Item as DropdownItem>::Value, SharedString)>,
+    max_chips: usize,
+    creatable: bool,
     empty: Option<Box<dyn Fn(&WindowContext) -> AnyElement + 'static>>,
     width: Length,
     menu_width: Length,
@@ -227,20 +461,64 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
 
 pub struct SearchableVec<T> {
     items: Vec<T>,
+    /// The titles of the items, to match them on the background executor.
+    titles: Arc<[SharedString]>,
     matched_items: Vec<T>,
+    /// The matched char positions in the titles of the `matched_items`.
+    matched_positions: Vec<Vec<usize>>,
+    cancel_flag: Arc<AtomicBool>,
 }
 
 impl<T: DropdownItem + Clone> SearchableVec<T> {
     pub fn new(items: impl Into<Vec<T>>) -> Self {
         let items = items.into();
         Self {
-            items: items.clone(),
-            matched_items: items,
+            titles: items.iter().map(|item| item.title()).collect(),
+            matched_positions: vec![vec![]; items.len()],
+            matched_items: items.clone(),
+            items,
+            cancel_flag: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// Push a new item, e.g.: the item created by the `DropdownEvent::Create`, it's also added to the current matches.
+    pub fn push(&mut self, item: T) {
+        self.titles = self
+            .titles
+            .iter()
+            .cloned()
+            .chain(Some(item.title()))
+            .collect();
+        self.matched_items.push(item.clone());
+        self.matched_positions.push(vec![]);
+        self.items.push(item);
+    }
+
+    fn set_matches(&mut self, mut matches: Vec<StringMatch>) {
+        // Keep the items of a section together, the matches are ranked inside each section.
+        let mut sections: Vec<Option<SharedString>> = vec![];
+        for item in &self.items {
+            let section = item.section();
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        if sections.len() > 1 {
+            matches.sort_by_cached_key(|m| {
+                let section = self.items[m.candidate_id].section();
+                sections.iter().position(|s| *s == section)
+            });
+        }
+
+        self.matched_items = matches
+            .iter()
+            .map(|m| self.items[m.candidate_id].clone())
+            .collect();
+        self.matched_positions = matches.into_iter().map(|m| m.positions).collect();
+    }
 }
 
-impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
+impl<T: DropdownItem + Clone + 'static> DropdownDelegate for SearchableVec<T> {
     type Item = T;
 
     fn len(&self) -> usize {
@@ -251,6 +529,13 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
         self.matched_items.get(ix)
     }
 
+    fn match_positions(&self, ix: usize) -> &[usize] {
+        self.matched_positions
+            .get(ix)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     fn position<V>(&self, value: &V) -> Option<usize>
     where
         Self::Item: DropdownItem<Value = V>,
@@ -269,24 +554,37 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
         true
     }
 
-    fn perform_search(&mut self, query: &str, _cx: &mut ViewContext<Dropdown<Self>>) -> Task<()> {
-        self.matched_items = self
-            .items
-            .iter()
-            .filter(|item| item.title().to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
-            .collect();
+    fn perform_search(&mut self, query: &str, cx: &mut ViewContext<Dropdown<Self>>) -> Task<()> {
+        // Stop the previous matching, its result is useless now.
+        self.cancel_flag.store(true, Ordering::Relaxed);
+        self.cancel_flag = Arc::new(AtomicBool::new(false));
+
+        let cancel_flag = self.cancel_flag.clone();
+        let matches = fuzzy::match_strings_in_background(
+            self.titles.clone(),
+            query,
+            cancel_flag.clone(),
+            cx.background_executor(),
+        );
+
+        cx.spawn(|dropdown, mut cx| async move {
+            let matches = matches.await;
+            if cancel_flag.load(Ordering::Relaxed) {
+                return;
+            }
 
-        Task::Ready(Some(()))
+            _ = dropdown.update(&mut cx, |dropdown, cx| {
+                dropdown.list.update(cx, |list, _| {
+                    list.delegate_mut().delegate.set_matches(matches);
+                });
+            });
+        })
     }
 }
 
 impl From<Vec<SharedString>> for SearchableVec<SharedString> {
     fn from(items: Vec<SharedString>) -> Self {
-        Self {
-            items: items.clone(),
-            matched_items: items,
-        }
+        Self::new(items)
     }
 }
 
@@ -300,11 +598,14 @@ where
         selected_index: Option<usize>,
         cx: &mut ViewContext<Self>,
     ) -> Self {
-        let delegate = DropdownListDelegate {
+        let mut delegate = DropdownListDelegate {
             delegate,
             dropdown: cx.view().downgrade(),
-            selected_index,
+            rows: vec![],
+            selected_index: None,
+            create_query: None,
         };
+        delegate.update_rows();
 
         let searchable = delegate.delegate.can_search();
 
@@ -323,6 +624,10 @@ where
             size: Size::Medium,
             icon: None,
             selected_value: None,
+            multiple: false,
+            selected_values: vec![],
+            max_chips: 3,
+            creatable: false,
             open: false,
             cleanable: false,
             title_prefix: None,
@@ -374,6 +679,30 @@ where
         self
     }
 
+    /// Set true to select multiple values, the menu stays open while the user toggles the items.
+    ///
+    /// Use [`Dropdown::selected_values`] to get the values, the `DropdownEvent::ConfirmMultiple` is emitted on change.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Set the max number of the selected values to show as chips, default: 3
+    ///
+    /// The dropdown input shows `N selected` instead if there are more values selected.
+    pub fn max_chips(mut self, max_chips: usize) -> Self {
+        self.max_chips = max_chips;
+        self
+    }
+
+    /// Set true to show a `Create "<query>"` row when the search query has no exact match.
+    ///
+    /// Confirming the row emits the `DropdownEvent::Create` with the query.
+    pub fn creatable(mut self, creatable: bool) -> Self {
+        self.creatable = creatable;
+        self
+    }
+
     pub fn empty<E, F>(mut self, f: F) -> Self
     where
         E: IntoElement,
@@ -389,25 +718,38 @@ where
         cx: &mut ViewContext<Self>,
     ) {
         self.list.update(cx, |list, cx| {
-            list.set_selected_index(selected_index, cx);
+            let row_ix = selected_index.and_then(|ix| list.delegate().row_index(ix));
+            list.set_selected_index(row_ix, cx);
         });
         self.update_selected_value(cx);
+        cx.notify();
     }
 
     pub fn set_selected_value(
         &mut self,
         selected_value: &<D::Item as DropdownItem>::Value,
         cx: &mut ViewContext<Self>,
-    ) where
-        <<D as DropdownDelegate>::Item as DropdownItem>::Value: PartialEq,
-    {
+    ) {
         let delegate = self.list.read(cx).delegate();
         let selected_index = delegate.delegate.position(selected_value);
         self.set_selected_index(selected_index, cx);
     }
 
+    /// Update the delegate, e.g.: add the item created by the `DropdownEvent::Create`, the menu rows are rebuilt after it.
+    pub fn update_delegate(&mut self, f: impl FnOnce(&mut D), cx: &mut ViewContext<Self>) {
+        self.list.update(cx, |list, cx| {
+            let delegate = list.delegate_mut();
+            f(&mut delegate.delegate);
+            delegate.update_rows();
+            cx.notify();
+        });
+    }
+
+    /// Return the selected item index, the section headers and separators are not counted.
     pub fn selected_index(&self, cx: &WindowContext) -> Option<usize> {
-        self.list.read(cx).selected_index()
+        let list = self.list.read(cx);
+        list.selected_index()
+            .and_then(|ix| list.delegate().item_index(ix))
     }
 
     fn update_selected_value(&mut self, cx: &WindowContext) {
@@ -421,6 +763,75 @@ where
         self.selected_value.as_ref()
     }
 
+    /// Return the selected values in the multiple mode, in the order of selection.
+    pub fn selected_values(&self) -> Vec<<D::Item as DropdownItem>::Value> {
+        self.selected_values
+            .iter()
+            .map(|(value, _)| value.clone())
+            .collect()
+    }
+
+    /// Set the selected values in the multiple mode, the values not in the items will be ignored.
+    pub fn set_selected_values(
+        &mut self,
+        values: impl IntoIterator<Item = <D::Item as DropdownItem>::Value>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let delegate = &self.list.read(cx).delegate().delegate;
+        self.selected_values = values
+            .into_iter()
+            .filter_map(|value| {
+                let title = delegate.get(delegate.position(&value)?)?.title();
+                Some((value, title))
+            })
+            .collect();
+        cx.notify();
+    }
+
+    /// Select all the items in the multiple mode, only the matched items if searching.
+    pub fn select_all(&mut self, cx: &mut ViewContext<Self>) {
+        let delegate = &self.list.read(cx).delegate().delegate;
+        let items: Vec<_> = (0..delegate.len())
+            .filter(|ix| !delegate.is_disabled(*ix))
+            .filter_map(|ix| delegate.get(ix))
+            .map(|item| (item.value().clone(), item.title()))
+            .collect();
+        for (value, title) in items {
+            if !self.is_selected(&value) {
+                self.selected_values.push((value, title));
+            }
+        }
+        cx.emit(DropdownEvent::ConfirmMultiple(self.selected_values()));
+        cx.notify();
+    }
+
+    /// Clear the selected values in the multiple mode.
+    pub fn clear_selection(&mut self, cx: &mut ViewContext<Self>) {
+        self.selected_values.clear();
+        cx.emit(DropdownEvent::ConfirmMultiple(vec![]));
+        cx.notify();
+    }
+
+    fn is_selected(&self, value: &<D::Item as DropdownItem>::Value) -> bool {
+        self.selected_values.iter().any(|(v, _)| v == value)
+    }
+
+    fn toggle_value(
+        &mut self,
+        value: <D::Item as DropdownItem>::Value,
+        title: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match self.selected_values.iter().position(|(v, _)| *v == value) {
+            Some(ix) => {
+                self.selected_values.remove(ix);
+            }
+            None => self.selected_values.push((value, title)),
+        }
+        cx.emit(DropdownEvent::ConfirmMultiple(self.selected_values()));
+        cx.notify();
+    }
+
     pub fn focus(&self, cx: &mut WindowContext) {
         self.focus_handle.focus(cx);
     }
@@ -466,26 +877,93 @@ where
     }
 
     fn clean(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
-        self.set_selected_index(None, cx)
+        if self.multiple {
+            self.clear_selection(cx);
+        } else {
+            self.set_selected_index(None, cx)
+        }
     }
 
     fn render_menu_content(&self, cx: &WindowContext) -> impl IntoElement {
+        let dropdown = self.list.read(cx).delegate().dropdown.clone();
+
         div()
             .track_focus(&self.list.focus_handle(cx))
             .on_mouse_down_out(|_, cx| {
                 cx.dispatch_action(Box::new(Escape));
             })
-            .map(|this| with_style(this, cx).child(self.list.clone()))
+            .map(|this| {
+                with_style(this, cx)
+                    .when(self.multiple, |this| {
+                        this.child(self.render_multiple_actions(dropdown, cx))
+                    })
+                    .child(self.list.clone())
+            })
+    }
+
+    /// Render the select all and clear actions at the top of the menu in the multiple mode.
+    fn render_multiple_actions(
+        &self,
+        dropdown: WeakView<Self>,
+        cx: &WindowContext,
+    ) -> impl IntoElement {
+        let action = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .cursor_pointer()
+                .hover(|this| this.text_color(cx.theme().foreground))
+                .child(label)
+        };
+        let clear_dropdown = dropdown.clone();
+
+        h_flex()
+            .justify_between()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(action("select-all", "Select All").on_click(move |_, cx| {
+                _ = dropdown.update(cx, |this, cx| this.select_all(cx));
+            }))
+            .child(action("clear", "Clear").on_click(move |_, cx| {
+                _ = clear_dropdown.update(cx, |this, cx| this.clear_selection(cx));
+            }))
     }
 
     fn display_title(&self, cx: &WindowContext) -> impl IntoElement {
-        if let Some(selected_index) = &self.selected_index(cx) {
+        if self.multiple && !self.selected_values.is_empty() {
+            let count = self.selected_values.len();
+
+            h_flex()
+                .gap_1()
+                .children(self.title_prefix.clone().map(|prefix| {
+                    div()
+                        .text_color(cx.theme().accent_foreground)
+                        .child(prefix.clone())
+                }))
+                .map(|this| {
+                    if count > self.max_chips {
+                        this.child(format!("{} selected", count))
+                    } else {
+                        this.children(self.selected_values.iter().map(|(_, title)| {
+                            div()
+                                .px_1p5()
+                                .rounded(px(cx.theme().radius))
+                                .bg(cx.theme().secondary)
+                                .whitespace_nowrap()
+                                .child(title.clone())
+                        }))
+                    }
+                })
+        } else if let Some(selected_index) = self.selected_index(cx).filter(|_| !self.multiple) {
             let title = self
                 .list
                 .read(cx)
                 .delegate()
                 .delegate
-                .get(*selected_index)
+                .get(selected_index)
                 .map(|item| item.title().to_string())
                 .unwrap_or_default();
 
@@ -541,7 +1019,12 @@ where
 {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let is_focused = self.focus_handle.is_focused(cx);
-        let show_clean = self.cleanable && self.selected_index(cx).is_some();
+        let show_clean = self.cleanable
+            && if self.multiple {
+                !self.selected_values.is_empty()
+            } else {
+                self.selected_index(cx).is_some()
+            };
 
         div()
             .id(self.id.clone())
@@ -0,0 +1,286 @@
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use gpui::{BackgroundExecutor, Task};
+
+/// The score of a matched char.
+const MATCH_SCORE: i32 = 16;
+/// The bonus of a match at the start of the candidate or a word, e.g.: `s` in `Dim Sum`.
+const WORD_START_BONUS: i32 = 8;
+/// The bonus of a match at a camel case boundary, e.g.: `S` in `DimSum`.
+const CAMEL_CASE_BONUS: i32 = 6;
+/// The bonus of a match right after the previous matched char.
+const CONSECUTIVE_BONUS: i32 = 5;
+/// The penalty of each skipped char before or between the matched chars.
+const GAP_PENALTY: i32 = 1;
+/// Check the cancel flag every this number of candidates.
+const CANCEL_CHECK_INTERVAL: usize = 1000;
+
+/// A matched candidate of [`match_strings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringMatch {
+    /// The index of the candidate in the candidates.
+    pub candidate_id: usize,
+    /// The higher score is the better match.
+    pub score: i32,
+    /// The byte offsets of the matched chars in the candidate, in ascending order.
+    pub positions: Vec<usize>,
+}
+
+fn eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Return the bonus of matching the char at `ix`, the word starts are preferred.
+fn char_bonus(chars: &[(usize, char)], ix: usize) -> i32 {
+    let c = chars[ix].1;
+    let Some(&(_, prev)) = ix.checked_sub(1).and_then(|ix| chars.get(ix)) else {
+        return WORD_START_BONUS;
+    };
+
+    if !prev.is_alphanumeric() && c.is_alphanumeric() {
+        WORD_START_BONUS
+    } else if (prev.is_lowercase() && c.is_uppercase()) || (!prev.is_numeric() && c.is_numeric()) {
+        CAMEL_CASE_BONUS
+    } else {
+        0
+    }
+}
+
+/// Match the `query` as a case insensitive subsequence of the `candidate`, the whitespaces in the query are ignored.
+///
+/// Returns the score and the byte offsets of the matched chars of the best match, or `None` if not matched.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(i32, Vec<usize>)> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some((0, vec![]));
+    }
+
+    let chars: Vec<(usize, char)> = candidate.char_indices().collect();
+    let (m, n) = (query.len(), chars.len());
+    if m > n {
+        return None;
+    }
+
+    // Quick reject before the scoring, most of the candidates are not matched.
+    let mut qi = 0;
+    for &(_, c) in &chars {
+        if qi < m && eq_ignore_case(query[qi], c) {
+            qi += 1;
+        }
+    }
+    if qi < m {
+        return None;
+    }
+
+    let bonuses: Vec<i32> = (0..n).map(|ix| char_bonus(&chars, ix)).collect();
+
+    // `scores[i * n + j]` is the best score of matching `query[..=i]` with `query[i]` at `chars[j]`,
+    // and `prevs[i * n + j]` is where the `query[i - 1]` matched in it.
+    let mut scores: Vec<Option<i32>> = vec![None; m * n];
+    let mut prevs = vec![0; m * n];
+    for i in 0..m {
+        // The best of `scores[i - 1][k] + GAP_PENALTY * k` for `k < j - 1`, to match after a gap.
+        let mut best_gap: Option<(i32, usize)> = None;
+
+        for j in i..n {
+            if i > 0 && j >= 2 {
+                let k = j - 2;
+                if let Some(score) = scores[(i - 1) * n + k] {
+                    let score = score + GAP_PENALTY * k as i32;
+                    if best_gap.map_or(true, |(best, _)| score > best) {
+                        best_gap = Some((score, k));
+                    }
+                }
+            }
+
+            if !eq_ignore_case(query[i], chars[j].1) {
+                continue;
+            }
+
+            let score = MATCH_SCORE + bonuses[j];
+            if i == 0 {
+                scores[j] = Some(score - GAP_PENALTY * j as i32);
+                continue;
+            }
+
+            let mut best = best_gap.map(|(best, k)| (best - GAP_PENALTY * (j - 1) as i32, k));
+            if let Some(prev_score) = scores[(i - 1) * n + j - 1] {
+                let prev_score = prev_score + CONSECUTIVE_BONUS;
+                if best.map_or(true, |(best, _)| prev_score >= best) {
+                    best = Some((prev_score, j - 1));
+                }
+            }
+
+            if let Some((prev_score, k)) = best {
+                scores[i * n + j] = Some(prev_score + score);
+                prevs[i * n + j] = k;
+            }
+        }
+    }
+
+    let (score, mut j) = (0..n)
+        .filter_map(|j| scores[(m - 1) * n + j].map(|score| (score, j)))
+        .max_by_key(|&(score, j)| (score, std::cmp::Reverse(j)))?;
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = chars[j].0;
+        j = prevs[i * n + j];
+    }
+
+    Some((score, positions))
+}
+
+/// Match the `query` with all the `candidates`, returns the matches ranked from the best.
+///
+/// The matches with the same score are ranked by the shorter candidate first, then the original order.
+/// All the candidates are returned in the original order if the query is empty.
+///
+/// Returns an empty result once the `cancel_flag` is set, e.g.: a new query is typed.
+pub fn match_strings<S: AsRef<str>>(
+    candidates: &[S],
+    query: &str,
+    cancel_flag: &AtomicBool,
+) -> Vec<StringMatch> {
+    if query.trim().is_empty() {
+        return (0..candidates.len())
+            .map(|candidate_id| StringMatch {
+                candidate_id,
+                score: 0,
+                positions: vec![],
+            })
+            .collect();
+    }
+
+    let mut matches = vec![];
+    for (candidate_id, candidate) in candidates.iter().enumerate() {
+        if candidate_id % CANCEL_CHECK_INTERVAL == 0 && cancel_flag.load(Ordering::Relaxed) {
+            return vec![];
+        }
+
+        if let Some((score, positions)) = fuzzy_match(query, candidate.as_ref()) {
+            matches.push(StringMatch {
+                candidate_id,
+                score,
+                positions,
+            });
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| {
+                let a_len = candidates[a.candidate_id].as_ref().len();
+                let b_len = candidates[b.candidate_id].as_ref().len();
+                a_len.cmp(&b_len)
+            })
+            .then(a.candidate_id.cmp(&b.candidate_id))
+    });
+    matches
+}
+
+/// Run the [`match_strings`] on the background executor, to keep the typing smooth with the large candidates.
+///
+/// Set the `cancel_flag` to stop the previous matching before starting a new one.
+pub fn match_strings_in_background<S>(
+    candidates: Arc<[S]>,
+    query: impl Into<String>,
+    cancel_flag: Arc<AtomicBool>,
+    executor: &BackgroundExecutor,
+) -> Task<Vec<StringMatch>>
+where
+    S: AsRef<str> + Send + Sync + 'static,
+{
+    let query = query.into();
+    executor.spawn(async move { match_strings(&candidates, &query, &cancel_flag) })
+}
+
+/// Convert the matched `positions` to the byte ranges of the `text` to highlight, the adjacent chars are merged.
+pub fn highlight_ranges(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for &pos in positions {
+        let Some(c) = text.get(pos..).and_then(|rest| rest.chars().next()) else {
+            continue;
+        };
+
+        let end = pos + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == pos => last.end = end,
+            _ => ranges.push(pos..end),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use super::{fuzzy_match, highlight_ranges, match_strings};
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "Sushi").unwrap().1, Vec::<usize>::new());
+        assert!(fuzzy_match("xyz", "Sushi").is_none());
+        assert!(fuzzy_match("ihsus", "Sushi").is_none());
+
+        // Case insensitive, and the whitespaces in the query are ignored.
+        assert_eq!(
+            fuzzy_match("SUSHI", "sushi").unwrap().1,
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            fuzzy_match("dim sum", "Dim Sum").unwrap().1,
+            vec![0, 1, 2, 4, 5, 6]
+        );
+
+        // Prefer the word starts and camel case boundaries.
+        assert_eq!(fuzzy_match("ds", "Dim Sum").unwrap().1, vec![0, 4]);
+        assert_eq!(fuzzy_match("ds", "DimSum").unwrap().1, vec![0, 3]);
+        assert_eq!(fuzzy_match("pt", "Pad Thai").unwrap().1, vec![0, 4]);
+
+        // Prefer the consecutive chars.
+        assert_eq!(fuzzy_match("ram", "Rare Ramen").unwrap().1, vec![5, 6, 7]);
+
+        // The positions are byte offsets.
+        assert_eq!(fuzzy_match("cr", "Crème brûlée").unwrap().1, vec![0, 1]);
+        assert_eq!(fuzzy_match("eb", "Crème brûlée").unwrap().1, vec![5, 7]);
+    }
+
+    #[test]
+    fn test_match_strings() {
+        let candidates = ["Pasta Carbonara", "Pizza", "Paella", "Pad Thai", "Sushi"];
+        let cancel_flag = AtomicBool::new(false);
+
+        let matches = match_strings(&candidates, "pa", &cancel_flag);
+        let ids: Vec<usize> = matches.iter().map(|m| m.candidate_id).collect();
+        // The same scores are ranked by the shorter first.
+        assert_eq!(ids, vec![2, 3, 0, 1]);
+
+        let matches = match_strings(&candidates, "pt", &cancel_flag);
+        assert_eq!(matches[0].candidate_id, 3);
+        assert_eq!(matches[0].positions, vec![0, 4]);
+
+        let matches = match_strings(&candidates, " ", &cancel_flag);
+        let ids: Vec<usize> = matches.iter().map(|m| m.candidate_id).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+
+        cancel_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        assert!(match_strings(&candidates, "pa", &cancel_flag).is_empty());
+    }
+
+    #[test]
+    fn test_highlight_ranges() {
+        assert_eq!(highlight_ranges("Dim Sum", &[0, 1, 4]), vec![0..2, 4..5]);
+        assert_eq!(highlight_ranges("Crème", &[2, 3]), vec![2..4]);
+        // The invalid positions are ignored.
+        assert_eq!(highlight_ranges("Crème", &[3, 10]), vec![]);
+    }
+}
@@ -27,6 +27,7 @@ pub enum IconName {
     EllipsisVertical,
     Eye,
     EyeOff,
+    Filter,
     GitHub,
     Globe,
     Heart,
@@ -75,6 +76,7 @@ impl IconName {
             IconName::EllipsisVertical => "icons/ellipsis-vertical.svg",
             IconName::Eye => "icons/eye.svg",
             IconName::EyeOff => "icons/eye-off.svg",
+            IconName::Filter => "icons/filter.svg",
             IconName::GitHub => "icons/github.svg",
             IconName::Globe => "icons/globe.svg",
             IconName::Heart => "icons/heart.svg",
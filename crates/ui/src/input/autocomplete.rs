@@ -0,0 +1,319 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, deferred, div, prelude::FluentBuilder as _, px, rems, AppContext, EventEmitter,
+    FocusHandle, FocusableView, InteractiveElement as _, IntoElement, KeyBinding, KeyContext,
+    ParentElement as _, Render, SharedString, Styled as _, Subscription, Task, View, ViewContext,
+    VisualContext as _, WeakView, WindowContext,
+};
+
+use crate::{
+    list::{List, ListDelegate, ListItem},
+    theme::ActiveTheme,
+    v_flex,
+};
+
+use super::{InputEvent, TextInput};
+
+actions!(autocomplete, [SelectPrev, SelectNext, Accept, Dismiss]);
+
+const CONTEXT: &str = "AutoComplete";
+/// The extra key context identifier when the suggestions are shown.
+const OPEN: &str = "open";
+
+pub fn init(cx: &mut AppContext) {
+    let context = format!("{} && {}", CONTEXT, OPEN);
+    let context = Some(context.as_str());
+    cx.bind_keys([
+        KeyBinding::new("up", SelectPrev, context),
+        KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("tab", Accept, context),
+        KeyBinding::new("escape", Dismiss, context),
+    ]);
+}
+
+/// A suggestion of the [`AutoComplete`], the `value` will be filled into the input when accepted.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub value: SharedString,
+    pub description: Option<SharedString>,
+}
+
+impl Suggestion {
+    pub fn new(value: impl Into<SharedString>) -> Self {
+        Self {
+            value: value.into(),
+            description: None,
+        }
+    }
+
+    /// Set the description to display after the value, e.g.: the type of the item.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+#[derive(Clone)]
+pub enum AutoCompleteEvent {
+    /// A suggestion was accepted by the tab / enter key or a click.
+    Accept(Suggestion),
+}
+
+struct SuggestionListDelegate {
+    autocomplete: WeakView<AutoComplete>,
+    suggestions: Vec<Suggestion>,
+    selected_index: Option<usize>,
+}
+
+impl ListDelegate for SuggestionListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self) -> usize {
+        self.suggestions.len()
+    }
+
+    fn render_item(&self, ix: usize, cx: &mut ViewContext<List<Self>>) -> Option<Self::Item> {
+        let suggestion = self.suggestions.get(ix)?;
+
+        Some(
+            ListItem::new(("suggestion", ix))
+                .selected(self.selected_index == Some(ix))
+                .child(
+                    div()
+                        .flex_1()
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .child(suggestion.value.clone()),
+                )
+                .when_some(suggestion.description.clone(), |this, description| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(description),
+                    )
+                }),
+        )
+    }
+
+    fn set_selected_index(&mut self, ix: Option<usize>, _: &mut ViewContext<List<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn confirm(&mut self, ix: Option<usize>, cx: &mut ViewContext<List<Self>>) {
+        let Some(suggestion) = ix.and_then(|ix| self.suggestions.get(ix)).cloned() else {
+            return;
+        };
+
+        if let Some(view) = self.autocomplete.upgrade() {
+            cx.update_view(&view, |view, cx| view.accept_suggestion(suggestion, cx));
+        }
+    }
+}
+
+/// A `TextInput` with a suggestions popup, the suggestions are loaded by an async provider as the user types.
+///
+/// The focus is always kept in the input, use up / down to select, tab / enter to accept and escape to dismiss.
+pub struct AutoComplete {
+    input: View<TextInput>,
+    list: View<List<SuggestionListDelegate>>,
+    provider: Rc<dyn Fn(&str, &mut WindowContext) -> Task<Vec<Suggestion>>>,
+    open: bool,
+    /// The accepted value, to avoid querying the suggestions again when it's filled into the input.
+    accepted_value: Option<SharedString>,
+    _query_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl AutoComplete {
+    pub fn new(
+        provider: impl Fn(&str, &mut WindowContext) -> Task<Vec<Suggestion>> + 'static,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let input = cx.new_view(TextInput::new);
+        let delegate = SuggestionListDelegate {
+            autocomplete: cx.view().downgrade(),
+            suggestions: vec![],
+            selected_index: None,
+        };
+        let list = cx.new_view(|cx| List::new(delegate, cx).no_query().max_h(rems(20.)));
+        let _subscriptions = vec![cx.subscribe(&input, Self::on_input_event)];
+
+        Self {
+            input,
+            list,
+            provider: Rc::new(provider),
+            open: false,
+            accepted_value: None,
+            _query_task: Task::Ready(None),
+            _subscriptions,
+        }
+    }
+
+    /// Returns the input of the autocomplete, to set the placeholder or subscribe to the `InputEvent`.
+    pub fn input(&self) -> &View<TextInput> {
+        &self.input
+    }
+
+    /// Return the text of the input field.
+    pub fn text(&self, cx: &AppContext) -> SharedString {
+        self.input.read(cx).text()
+    }
+
+    pub fn focus(&self, cx: &mut ViewContext<Self>) {
+        self.input.focus_handle(cx).focus(cx);
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: View<TextInput>,
+        event: &InputEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                if self.accepted_value.take().as_ref() == Some(text) {
+                    return;
+                }
+                self.query(text.clone(), cx);
+            }
+            InputEvent::PressEnter => self.accept(&Accept, cx),
+            InputEvent::Blur => self.dismiss(&Dismiss, cx),
+            _ => {}
+        }
+    }
+
+    fn query(&mut self, text: SharedString, cx: &mut ViewContext<Self>) {
+        if text.is_empty() {
+            self._query_task = Task::Ready(None);
+            self.dismiss(&Dismiss, cx);
+            return;
+        }
+
+        let suggestions = (self.provider)(&text, cx);
+        self._query_task = cx.spawn(|this, mut cx| async move {
+            let suggestions = suggestions.await;
+
+            _ = this.update(&mut cx, |this, cx| {
+                this.open = !suggestions.is_empty();
+                let selected_index = this.open.then_some(0);
+                this.list.update(cx, |list, cx| {
+                    list.delegate_mut().suggestions = suggestions;
+                    list.set_selected_index(selected_index, cx);
+                    list.scroll_to_selected_item(cx);
+                });
+                cx.notify();
+            });
+        });
+    }
+
+    fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        self.move_selection(-1, cx);
+    }
+
+    fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        self.move_selection(1, cx);
+    }
+
+    fn move_selection(&mut self, delta: isize, cx: &mut ViewContext<Self>) {
+        self.list.update(cx, |list, cx| {
+            let count = list.delegate().items_count() as isize;
+            if count == 0 {
+                return;
+            }
+
+            let ix = match list.selected_index() {
+                Some(ix) => (ix as isize + delta).rem_euclid(count),
+                None if delta > 0 => 0,
+                None => count - 1,
+            };
+            list.set_selected_index(Some(ix as usize), cx);
+            list.scroll_to_selected_item(cx);
+            cx.notify();
+        });
+    }
+
+    fn accept(&mut self, _: &Accept, cx: &mut ViewContext<Self>) {
+        if !self.open {
+            return;
+        }
+
+        let list = self.list.read(cx);
+        let suggestion = list
+            .selected_index()
+            .and_then(|ix| list.delegate().suggestions.get(ix))
+            .cloned();
+        if let Some(suggestion) = suggestion {
+            self.accept_suggestion(suggestion, cx);
+        }
+    }
+
+    fn accept_suggestion(&mut self, suggestion: Suggestion, cx: &mut ViewContext<Self>) {
+        self.open = false;
+        self._query_task = Task::Ready(None);
+        self.accepted_value = Some(suggestion.value.clone());
+
+        let value = suggestion.value.clone();
+        self.input.update(cx, |input, cx| {
+            input.set_text(value, cx);
+            input.focus(cx);
+        });
+        cx.emit(AutoCompleteEvent::Accept(suggestion));
+        cx.notify();
+    }
+
+    fn dismiss(&mut self, _: &Dismiss, cx: &mut ViewContext<Self>) {
+        self.open = false;
+        cx.notify();
+    }
+}
+
+impl EventEmitter<AutoCompleteEvent> for AutoComplete {}
+
+impl FocusableView for AutoComplete {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl Render for AutoComplete {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let mut key_context = KeyContext::default();
+        key_context.add(CONTEXT);
+        if self.open {
+            key_context.add(OPEN);
+        }
+
+        div()
+            .key_context(key_context)
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::accept))
+            .on_action(cx.listener(Self::dismiss))
+            .relative()
+            .w_full()
+            .child(self.input.clone())
+            .when(self.open, |this| {
+                this.child(
+                    deferred(
+                        v_flex()
+                            .occlude()
+                            .absolute()
+                            .top_full()
+                            .left_0()
+                            .w_full()
+                            .mt_1p5()
+                            .bg(cx.theme().background)
+                            .border_1()
+                            .border_color(cx.theme().input)
+                            .rounded(px(cx.theme().radius))
+                            .shadow_md()
+                            .overflow_hidden()
+                            .child(self.list.clone()),
+                    )
+                    .with_priority(1),
+                )
+            })
+    }
+}
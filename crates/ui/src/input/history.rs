@@ -4,6 +4,8 @@ use std::{
     time::{Duration, Instant},
 };
 
+use serde::{Deserialize, Serialize};
+
 const MAX_UNDO: usize = 1000;
 /// Group interval in milliseconds
 const GROUP_INTERVAL: u64 = 1000;
@@ -14,18 +16,33 @@ pub struct History {
     redos: Vec<Change>,
     last_changed_at: Instant,
     version: usize,
+    /// The depth of the nested transactions, the changes are grouped into one version when > 0.
+    transaction_depth: usize,
+    /// Start a new version for the next change, e.g.: after a transaction is ended.
+    new_group: bool,
     pub(crate) ignore: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Change {
     pub(crate) old_range: Range<usize>,
     pub(crate) old_text: String,
     pub(crate) new_range: Range<usize>,
     pub(crate) new_text: String,
+    /// The selection before the change, to restore it on undo.
+    pub(crate) old_selection: Range<usize>,
     version: usize,
 }
 
+/// The undo and redo stacks of a `TextInput`, to keep the history across re-creating the view.
+///
+/// It's serializable, so it can also be persisted along with the text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistorySnapshot {
+    undos: Vec<Change>,
+    redos: Vec<Change>,
+}
+
 impl History {
     pub fn new() -> Self {
         Self {
@@ -34,83 +51,194 @@ impl History {
             ignore: false,
             last_changed_at: Instant::now(),
             version: 0,
+            transaction_depth: 0,
+            new_group: false,
         }
     }
 
     /// Increment the version number if the last change was made more than `GROUP_INTERVAL` milliseconds ago.
     fn inc_version(&mut self) -> usize {
         let t = Instant::now();
-        if self.last_changed_at.elapsed().as_millis()
-            > Duration::from_millis(GROUP_INTERVAL).as_millis()
+        if self.transaction_depth == 0
+            && (self.new_group
+                || self.last_changed_at.elapsed().as_millis()
+                    > Duration::from_millis(GROUP_INTERVAL).as_millis())
         {
             self.version += 1;
+            self.new_group = false;
         }
 
         self.last_changed_at = t;
         self.version
     }
 
+    /// Start a transaction, all changes until the `end_transaction` will be undone as one step.
+    ///
+    /// The transactions can be nested, only the outermost one takes effect.
+    pub fn start_transaction(&mut self) {
+        if self.transaction_depth == 0 {
+            self.version += 1;
+        }
+        self.transaction_depth += 1;
+    }
+
+    /// End the transaction started by `start_transaction`.
+    pub fn end_transaction(&mut self) {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+        if self.transaction_depth == 0 {
+            self.new_group = true;
+        }
+    }
+
     pub fn push(
         &mut self,
         old_range: Range<usize>,
         old_text: &str,
         new_range: Range<usize>,
         new_text: &str,
+        old_selection: Range<usize>,
     ) {
         let version = self.inc_version();
 
         if self.undos.len() >= MAX_UNDO {
             self.undos.remove(0);
         }
+        self.redos.clear();
         self.undos.push(Change {
             old_range,
             old_text: old_text.to_string(),
             new_range,
             new_text: new_text.to_string(),
+            old_selection,
             version,
         });
     }
 
+    /// Returns true if there are changes to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undos.is_empty()
+    }
+
+    /// Returns true if there are undone changes to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redos.is_empty()
+    }
+
+    /// Returns the changes of the last version, from the newest to the oldest.
     pub fn undo(&mut self) -> Option<Vec<Change>> {
         if let Some(first_change) = self.undos.pop() {
             let mut changes = vec![first_change.clone()];
             // pick the next all changes with the same version
             while self
                 .undos
-                .iter()
-                .filter(|c| c.version == first_change.version)
-                .count()
-                > 0
+                .last()
+                .map_or(false, |c| c.version == first_change.version)
             {
                 let change = self.undos.pop().unwrap();
                 changes.push(change);
             }
 
-            self.redos.extend(changes.iter().rev().cloned());
+            self.redos.extend(changes.iter().cloned());
             Some(changes)
         } else {
             None
         }
     }
 
+    /// Returns the changes of the last undone version, from the oldest to the newest.
     pub fn redo(&mut self) -> Option<Vec<Change>> {
         if let Some(first_change) = self.redos.pop() {
             let mut changes = vec![first_change.clone()];
             // pick the next all changes with the same version
             while self
                 .redos
-                .iter()
-                .filter(|c| c.version == first_change.version)
-                .count()
-                > 0
+                .last()
+                .map_or(false, |c| c.version == first_change.version)
             {
                 let change = self.redos.pop().unwrap();
                 changes.push(change);
             }
-            self.undos.extend(changes.iter().rev().cloned());
+            self.undos.extend(changes.iter().cloned());
             Some(changes)
         } else {
             None
         }
     }
+
+    /// Export the undo and redo stacks.
+    pub fn snapshot(&self) -> HistorySnapshot {
+        HistorySnapshot {
+            undos: self.undos.clone(),
+            redos: self.redos.clone(),
+        }
+    }
+
+    /// Replace the undo and redo stacks with the snapshot, the next change will start a new version.
+    pub fn restore(&mut self, snapshot: HistorySnapshot) {
+        self.version = snapshot
+            .undos
+            .iter()
+            .chain(snapshot.redos.iter())
+            .map(|c| c.version)
+            .max()
+            .unwrap_or(0)
+            .max(self.version);
+        self.undos = snapshot.undos;
+        self.redos = snapshot.redos;
+        self.new_group = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    #[test]
+    fn test_transaction() {
+        let mut history = History::new();
+        history.push(0..0, "", 0..1, "a", 0..0);
+
+        history.start_transaction();
+        history.push(1..1, "", 1..2, "b", 1..1);
+        history.start_transaction();
+        history.push(2..2, "", 2..3, "c", 2..2);
+        history.end_transaction();
+        history.end_transaction();
+
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+        let changes = history.undo().unwrap();
+        assert!(history.can_redo());
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].new_text, "c");
+        assert_eq!(changes[1].new_text, "b");
+        assert_eq!(changes[1].old_selection, 1..1);
+
+        let changes = history.redo().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].new_text, "b");
+        assert_eq!(changes[1].new_text, "c");
+
+        // The change after the transaction is not grouped into it.
+        history.push(3..3, "", 3..4, "d", 3..3);
+        assert_eq!(history.undo().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut history = History::new();
+        history.push(0..0, "", 0..1, "a", 0..0);
+        history.push(1..1, "", 1..2, "b", 1..1);
+        history.undo();
+
+        let snapshot = history.snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+
+        let mut history = History::new();
+        history.restore(serde_json::from_str(&json).unwrap());
+        history.push(2..2, "", 2..3, "c", 2..2);
+        assert!(history.redo().is_none());
+        assert_eq!(history.undo().unwrap()[0].new_text, "c");
+        assert!(history.undo().is_none());
+    }
 }
@@ -3,25 +3,33 @@
 //! Based on the `Input` example from the `gpui` crate.
 //! https://github.com/zed-industries/zed/blob/main/crates/gpui/examples/input.rs
 
+use std::cell::Cell;
 use std::ops::Range;
+use std::rc::Rc;
 
 use super::blink_cursor::BlinkCursor;
-use super::history::History;
+use super::history::{History, HistorySnapshot};
 use super::ClearButton;
+use super::MaskPattern;
+use crate::context_menu::ContextMenuExt as _;
 use crate::indicator::Indicator;
+use crate::popup_menu::PopupMenu;
+use crate::scroll::{Scrollbar, ScrollbarState};
 use crate::styled_ext::{Sizable, StyleSized};
 use crate::theme::ActiveTheme;
 use crate::{event::InteractiveElementExt as _, Size};
-use crate::{Clickable as _, StyledExt as _};
+use crate::{v_flex, Clickable as _, StyledExt as _};
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
     actions, div, fill, point, px, relative, rems, size, AnyElement, AppContext, Bounds,
     ClickEvent, ClipboardItem, Context as _, Element, ElementId, ElementInputHandler, EventEmitter,
-    FocusHandle, FocusableView, GlobalElementId, InteractiveElement as _, IntoElement, KeyBinding,
-    KeyDownEvent, LayoutId, Model, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    PaintQuad, ParentElement as _, Pixels, Point, Render, ShapedLine, SharedString, Style,
-    Styled as _, TextRun, UnderlineStyle, View, ViewContext, ViewInputHandler, WindowContext,
+    FocusHandle, FocusOutEvent, FocusableView, GlobalElementId, Hsla, InteractiveElement as _,
+    IntoElement, KeyBinding, KeyContext, KeyDownEvent, LayoutId, Model, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement as _, Pixels, Point,
+    Render, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString, Style, Styled as _, Task,
+    TextRun, UnderlineStyle, View, ViewContext, ViewInputHandler, WindowContext, WrappedLine,
 };
+use smallvec::SmallVec;
 use unicode_segmentation::*;
 
 actions!(
@@ -32,8 +40,12 @@ actions!(
         Enter,
         Left,
         Right,
+        Up,
+        Down,
         SelectLeft,
         SelectRight,
+        SelectUp,
+        SelectDown,
         SelectAll,
         Home,
         End,
@@ -47,6 +59,13 @@ actions!(
         Redo,
         MoveToStartOfLine,
         MoveToEndOfLine,
+        MoveToPreviousWord,
+        MoveToNextWord,
+        SelectToPreviousWord,
+        SelectToNextWord,
+        DeleteToPreviousWordStart,
+        DeleteToNextWordEnd,
+        DeleteToBeginningOfLine,
         TextChanged,
     ]
 );
@@ -57,9 +76,20 @@ pub enum InputEvent {
     PressEnter,
     Focus,
     Blur,
+    /// The result of the `validation` or `async_validation`, the error is the message to show.
+    Validate(Result<(), SharedString>),
+}
+
+/// The validation of the input text, it accepts the text but marks the input field invalid.
+enum Validation {
+    Sync(Box<dyn Fn(&str) -> Result<(), SharedString> + 'static>),
+    Async(Box<dyn Fn(&str, &mut WindowContext) -> Task<Result<(), SharedString>> + 'static>),
 }
 
 const CONTEXT: &str = "Input";
+/// The extra key context identifier for the multi-line mode.
+const MULTI_LINE: &str = "multi_line";
+const MULTI_LINE_CONTEXT: &str = "Input && multi_line";
 
 pub fn init(cx: &mut AppContext) {
     cx.bind_keys([
@@ -68,13 +98,43 @@ pub fn init(cx: &mut AppContext) {
         KeyBinding::new("enter", Enter, Some(CONTEXT)),
         KeyBinding::new("left", Left, Some(CONTEXT)),
         KeyBinding::new("right", Right, Some(CONTEXT)),
+        KeyBinding::new("up", Up, Some(MULTI_LINE_CONTEXT)),
+        KeyBinding::new("down", Down, Some(MULTI_LINE_CONTEXT)),
         KeyBinding::new("shift-left", SelectLeft, Some(CONTEXT)),
         KeyBinding::new("shift-right", SelectRight, Some(CONTEXT)),
+        KeyBinding::new("shift-up", SelectUp, Some(MULTI_LINE_CONTEXT)),
+        KeyBinding::new("shift-down", SelectDown, Some(MULTI_LINE_CONTEXT)),
         KeyBinding::new("home", Home, Some(CONTEXT)),
         KeyBinding::new("end", End, Some(CONTEXT)),
         KeyBinding::new("shift-home", SelectToHome, Some(CONTEXT)),
         KeyBinding::new("shift-end", SelectToEnd, Some(CONTEXT)),
         #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-left", MoveToPreviousWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-left", MoveToPreviousWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-right", MoveToNextWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-right", MoveToNextWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-shift-left", SelectToPreviousWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-left", SelectToPreviousWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-shift-right", SelectToNextWord, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-right", SelectToNextWord, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-backspace", DeleteToPreviousWordStart, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-backspace", DeleteToPreviousWordStart, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("alt-delete", DeleteToNextWordEnd, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-delete", DeleteToNextWordEnd, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-backspace", DeleteToBeginningOfLine, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
         KeyBinding::new("ctrl-cmd-space", ShowCharacterPalette, Some(CONTEXT)),
         #[cfg(target_os = "macos")]
         KeyBinding::new("cmd-a", SelectAll, Some(CONTEXT)),
@@ -120,9 +180,19 @@ pub struct TextInput {
     selection_reversed: bool,
     marked_range: Option<Range<usize>>,
     last_layout: Option<ShapedLine>,
+    /// The wrapped lines of the last layout in multi-line mode.
+    last_lines: Option<SmallVec<[WrappedLine; 1]>>,
+    last_line_height: Pixels,
+    /// The cursor offset of the last paint, used to scroll the cursor into view only when it moved.
+    last_cursor_offset: Option<usize>,
     last_bounds: Option<Bounds<Pixels>>,
     scroll_offset: Point<Pixels>,
+    scroll_handle: ScrollHandle,
+    scrollbar_state: Rc<Cell<ScrollbarState>>,
+    scroll_size: gpui::Size<Pixels>,
     is_selecting: bool,
+    multi_line: bool,
+    rows: usize,
     disabled: bool,
     masked: bool,
     appearance: bool,
@@ -130,6 +200,15 @@ pub struct TextInput {
     size: Size,
     pattern: Option<regex::Regex>,
     validate: Option<Box<dyn Fn(&str) -> bool + 'static>>,
+    mask_pattern: Option<MaskPattern>,
+    validation: Option<Validation>,
+    validation_error: Option<SharedString>,
+    validating: bool,
+    _validation_task: Task<()>,
+    /// The highlighted ranges with the background colors, e.g.: the matches of a find bar.
+    highlights: Vec<(Range<usize>, Hsla)>,
+    /// The builder to append the app items to the context menu.
+    context_menu: Option<Rc<dyn Fn(PopupMenu, &mut WindowContext) -> PopupMenu + 'static>>,
 }
 
 impl EventEmitter<InputEvent> for TextInput {}
@@ -149,9 +228,17 @@ impl TextInput {
             selection_reversed: false,
             marked_range: None,
             last_layout: None,
+            last_lines: None,
+            last_line_height: px(20.),
+            last_cursor_offset: None,
             last_bounds: None,
             scroll_offset: point(px(0.), px(0.)),
+            scroll_handle: ScrollHandle::new(),
+            scrollbar_state: Rc::new(Cell::new(ScrollbarState::new())),
+            scroll_size: gpui::Size::default(),
             is_selecting: false,
+            multi_line: false,
+            rows: 2,
             disabled: false,
             masked: false,
             appearance: true,
@@ -162,6 +249,13 @@ impl TextInput {
             size: Size::Medium,
             pattern: None,
             validate: None,
+            mask_pattern: None,
+            validation: None,
+            validation_error: None,
+            validating: false,
+            _validation_task: Task::Ready(None),
+            highlights: vec![],
+            context_menu: None,
         };
 
         // Observe the blink cursor to repaint the view when it changes.
@@ -181,7 +275,8 @@ impl TextInput {
         .detach();
 
         cx.on_focus(&focus_handle, Self::on_focus).detach();
-        cx.on_blur(&focus_handle, Self::on_blur).detach();
+        // Blur when the focus leaves the input and its context menu, the selection is kept for the menu actions.
+        cx.on_focus_out(&focus_handle, Self::on_blur).detach();
 
         input
     }
@@ -197,6 +292,51 @@ impl TextInput {
         cx.notify();
     }
 
+    /// Replace the text in the range (in bytes) with the new text, the change can be undone.
+    pub fn replace_text(
+        &mut self,
+        range: Range<usize>,
+        new_text: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let range_utf16 = self.range_to_utf16(&range);
+        self.replace_text_in_range(Some(range_utf16), new_text, cx);
+    }
+
+    /// Start a transaction, the changes until [`TextInput::end_transaction`] will be undone as one step.
+    pub fn start_transaction(&mut self) {
+        self.history.start_transaction();
+    }
+
+    /// End the transaction started by [`TextInput::start_transaction`].
+    pub fn end_transaction(&mut self) {
+        self.history.end_transaction();
+    }
+
+    /// Run the function in a transaction, see [`TextInput::start_transaction`].
+    pub fn transact<R>(
+        &mut self,
+        cx: &mut ViewContext<Self>,
+        f: impl FnOnce(&mut Self, &mut ViewContext<Self>) -> R,
+    ) -> R {
+        self.start_transaction();
+        let result = f(self, cx);
+        self.end_transaction();
+        result
+    }
+
+    /// Export the undo history, to import it by [`TextInput::import_history`] after re-creating the input.
+    pub fn export_history(&self) -> HistorySnapshot {
+        self.history.snapshot()
+    }
+
+    /// Import the undo history exported by [`TextInput::export_history`].
+    ///
+    /// The text should be the same as the time it was exported, otherwise the undo will be wrong.
+    pub fn import_history(&mut self, snapshot: HistorySnapshot) {
+        self.history.restore(snapshot);
+    }
+
     /// Set the disabled state of the input field.
     pub fn set_disabled(&mut self, disabled: bool, cx: &mut ViewContext<Self>) {
         self.disabled = disabled;
@@ -229,6 +369,25 @@ impl TextInput {
         cx.notify();
     }
 
+    /// Set the input field to multi-line mode (TextArea).
+    ///
+    /// The text will be wrapped by the width of the input, and the Enter key inserts a new line.
+    pub fn multi_line(mut self) -> Self {
+        self.multi_line = true;
+        self
+    }
+
+    /// Set the number of visible rows in multi-line mode, default is 2.
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    /// Return true if the input field is in multi-line mode.
+    pub fn is_multi_line(&self) -> bool {
+        self.multi_line
+    }
+
     /// Set the appearance of the input field.
     pub fn appearance(mut self, appearance: bool) -> Self {
         self.appearance = appearance;
@@ -284,6 +443,126 @@ impl TextInput {
         self
     }
 
+    /// Append the items to the right-click context menu, after the built-in edit items.
+    pub fn context_menu(
+        mut self,
+        f: impl Fn(PopupMenu, &mut WindowContext) -> PopupMenu + 'static,
+    ) -> Self {
+        self.context_menu = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the mask pattern of the input field, the text will be formatted as the user types.
+    ///
+    /// See [`MaskPattern`] for the supported patterns, use [`TextInput::unmask_value`] to get the raw value.
+    pub fn mask_pattern(mut self, mask_pattern: impl Into<MaskPattern>) -> Self {
+        self.mask_pattern = Some(mask_pattern.into());
+        self
+    }
+
+    /// Set the mask pattern of the input field, and format the current text with it.
+    pub fn set_mask_pattern(
+        &mut self,
+        mask_pattern: impl Into<MaskPattern>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.mask_pattern = Some(mask_pattern.into());
+        let text = self.unmask_value();
+        self.set_text(text, cx);
+    }
+
+    /// Set the validation function of the input field.
+    ///
+    /// Unlike [`TextInput::validate`], the text is always accepted, the input field will be marked invalid
+    /// and show the error message below when the function returns `Err`.
+    pub fn validation(mut self, f: impl Fn(&str) -> Result<(), SharedString> + 'static) -> Self {
+        self.validation = Some(Validation::Sync(Box::new(f)));
+        self
+    }
+
+    /// Set the async validation function of the input field, e.g.: check the username is available.
+    ///
+    /// The loading indicator will be shown while validating, see [`TextInput::validation`].
+    pub fn async_validation(
+        mut self,
+        f: impl Fn(&str, &mut WindowContext) -> Task<Result<(), SharedString>> + 'static,
+    ) -> Self {
+        self.validation = Some(Validation::Async(Box::new(f)));
+        self
+    }
+
+    /// Return the error message of the last validation, `None` if the text is valid.
+    pub fn validation_error(&self) -> Option<&SharedString> {
+        self.validation_error.as_ref()
+    }
+
+    /// Set the highlighted ranges (in bytes) with the background colors, they are painted under the text.
+    ///
+    /// The highlights are cleared when the text changes.
+    pub fn set_highlights(
+        &mut self,
+        highlights: impl IntoIterator<Item = (Range<usize>, Hsla)>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.highlights = highlights.into_iter().collect();
+        self.highlights.sort_by_key(|(range, _)| range.start);
+        cx.notify();
+    }
+
+    /// Return the highlighted ranges, sorted by the start.
+    pub fn highlights(&self) -> &[(Range<usize>, Hsla)] {
+        &self.highlights
+    }
+
+    pub fn clear_highlights(&mut self, cx: &mut ViewContext<Self>) {
+        self.highlights.clear();
+        cx.notify();
+    }
+
+    /// Select the next highlighted range after the selection, and scroll it into view.
+    ///
+    /// Returns the index of the selected highlight, it wraps around to the first one at the end.
+    pub fn select_next_highlight(&mut self, cx: &mut ViewContext<Self>) -> Option<usize> {
+        let ix = self
+            .highlights
+            .iter()
+            .position(|(range, _)| range.start >= self.selected_range.end)
+            .or((!self.highlights.is_empty()).then_some(0))?;
+        self.select_highlight(ix, cx);
+        Some(ix)
+    }
+
+    /// Select the previous highlighted range before the selection, and scroll it into view.
+    ///
+    /// Returns the index of the selected highlight, it wraps around to the last one at the start.
+    pub fn select_prev_highlight(&mut self, cx: &mut ViewContext<Self>) -> Option<usize> {
+        let ix = self
+            .highlights
+            .iter()
+            .rposition(|(range, _)| range.end <= self.selected_range.start)
+            .or(self.highlights.len().checked_sub(1))?;
+        self.select_highlight(ix, cx);
+        Some(ix)
+    }
+
+    fn select_highlight(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some((range, _)) = self.highlights.get(ix) else {
+            return;
+        };
+
+        self.selected_range = range.clone();
+        self.selection_reversed = false;
+        cx.notify();
+    }
+
+    /// Returns the highlights to paint, the masked text doesn't show the highlights.
+    fn visible_highlights(&self) -> impl Iterator<Item = &(Range<usize>, Hsla)> {
+        let len = self.text.len();
+        self.highlights
+            .iter()
+            .filter(move |(range, _)| !self.masked && !range.is_empty() && range.end <= len)
+    }
+
     /// Set true to show indicator at the input right.
     pub fn set_loading(&mut self, loading: bool, cx: &mut ViewContext<Self>) {
         self.loading = loading;
@@ -295,6 +574,16 @@ impl TextInput {
         self.text.clone()
     }
 
+    /// Return the text without the mask pattern separators, e.g.: `5551234567` for `(555) 123-4567`.
+    ///
+    /// It's the same as [`TextInput::text`] if no mask pattern is set.
+    pub fn unmask_value(&self) -> SharedString {
+        match &self.mask_pattern {
+            Some(mask_pattern) => mask_pattern.unmask(&self.text).into(),
+            None => self.text.clone(),
+        }
+    }
+
     pub fn disabled(&self) -> bool {
         self.disabled
     }
@@ -321,6 +610,34 @@ impl TextInput {
         }
     }
 
+    fn up(&mut self, _: &Up, cx: &mut ViewContext<Self>) {
+        self.pause_blink_cursor(cx);
+        let offset = if self.selected_range.is_empty() {
+            self.cursor_offset()
+        } else {
+            self.selected_range.start
+        };
+        self.move_to(self.offset_for_row_delta(offset, -1), cx);
+    }
+
+    fn down(&mut self, _: &Down, cx: &mut ViewContext<Self>) {
+        self.pause_blink_cursor(cx);
+        let offset = if self.selected_range.is_empty() {
+            self.cursor_offset()
+        } else {
+            self.selected_range.end
+        };
+        self.move_to(self.offset_for_row_delta(offset, 1), cx);
+    }
+
+    fn select_up(&mut self, _: &SelectUp, cx: &mut ViewContext<Self>) {
+        self.select_to(self.offset_for_row_delta(self.cursor_offset(), -1), cx);
+    }
+
+    fn select_down(&mut self, _: &SelectDown, cx: &mut ViewContext<Self>) {
+        self.select_to(self.offset_for_row_delta(self.cursor_offset(), 1), cx);
+    }
+
     fn select_left(&mut self, _: &SelectLeft, cx: &mut ViewContext<Self>) {
         self.select_to(self.previous_boundary(self.cursor_offset()), cx);
     }
@@ -336,20 +653,46 @@ impl TextInput {
 
     fn home(&mut self, _: &Home, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        self.move_to(0, cx);
+        self.move_to(self.start_of_line(self.cursor_offset()), cx);
     }
 
     fn end(&mut self, _: &End, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        self.move_to(self.text.len(), cx);
+        self.move_to(self.end_of_line(self.cursor_offset()), cx);
     }
 
     fn select_to_home(&mut self, _: &SelectToHome, cx: &mut ViewContext<Self>) {
-        self.select_to(0, cx);
+        self.select_to(self.start_of_line(self.cursor_offset()), cx);
     }
 
     fn select_to_end(&mut self, _: &SelectToEnd, cx: &mut ViewContext<Self>) {
-        self.select_to(self.text.len(), cx);
+        self.select_to(self.end_of_line(self.cursor_offset()), cx);
+    }
+
+    fn move_to_previous_word(&mut self, _: &MoveToPreviousWord, cx: &mut ViewContext<Self>) {
+        let offset = if self.selected_range.is_empty() {
+            self.cursor_offset()
+        } else {
+            self.selected_range.start
+        };
+        self.move_to(self.previous_word_start(offset), cx);
+    }
+
+    fn move_to_next_word(&mut self, _: &MoveToNextWord, cx: &mut ViewContext<Self>) {
+        let offset = if self.selected_range.is_empty() {
+            self.cursor_offset()
+        } else {
+            self.selected_range.end
+        };
+        self.move_to(self.next_word_end(offset), cx);
+    }
+
+    fn select_to_previous_word(&mut self, _: &SelectToPreviousWord, cx: &mut ViewContext<Self>) {
+        self.select_to(self.previous_word_start(self.cursor_offset()), cx);
+    }
+
+    fn select_to_next_word(&mut self, _: &SelectToNextWord, cx: &mut ViewContext<Self>) {
+        self.select_to(self.next_word_end(self.cursor_offset()), cx);
     }
 
     fn backspace(&mut self, _: &Backspace, cx: &mut ViewContext<Self>) {
@@ -368,8 +711,52 @@ impl TextInput {
         self.pause_blink_cursor(cx);
     }
 
+    fn delete_to_previous_word_start(
+        &mut self,
+        _: &DeleteToPreviousWordStart,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.previous_word_start(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", cx);
+        self.pause_blink_cursor(cx);
+    }
+
+    fn delete_to_next_word_end(&mut self, _: &DeleteToNextWordEnd, cx: &mut ViewContext<Self>) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.next_word_end(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", cx);
+        self.pause_blink_cursor(cx);
+    }
+
+    fn delete_to_beginning_of_line(
+        &mut self,
+        _: &DeleteToBeginningOfLine,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if self.selected_range.is_empty() {
+            let offset = self.cursor_offset();
+            let start = self.start_of_line(offset);
+            // Delete the line break when the cursor is already at the line start.
+            if start == offset {
+                self.select_to(self.previous_boundary(offset), cx)
+            } else {
+                self.select_to(start, cx)
+            }
+        }
+        self.replace_text_in_range(None, "", cx);
+        self.pause_blink_cursor(cx);
+    }
+
     fn enter(&mut self, _: &Enter, cx: &mut ViewContext<Self>) {
-        cx.emit(InputEvent::PressEnter);
+        if self.multi_line {
+            self.replace_text_in_range(None, "\n", cx);
+            self.pause_blink_cursor(cx);
+        } else {
+            cx.emit(InputEvent::PressEnter);
+        }
     }
 
     fn clean(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
@@ -393,6 +780,45 @@ impl TextInput {
         }
     }
 
+    fn on_secondary_mouse_down(&mut self, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+        if event.button != MouseButton::Right {
+            return;
+        }
+
+        // Focus before the context menu is opened, then the focus will be restored to the input after it's dismissed.
+        self.focus(cx);
+        let offset = self.index_for_mouse_position(event.position);
+        if !(self.selected_range.start..=self.selected_range.end).contains(&offset) {
+            self.move_to(offset, cx);
+        }
+    }
+
+    /// Build the built-in items of the context menu, the enabled states are by the selection, clipboard and history.
+    fn build_context_menu(&self, menu: PopupMenu, cx: &AppContext) -> PopupMenu {
+        let editable = !self.disabled;
+        let has_selection = !self.selected_range.is_empty() && !self.masked;
+        let has_clipboard = cx
+            .read_from_clipboard()
+            .map_or(false, |clipboard| !clipboard.text().is_empty());
+
+        menu.menu_with_disabled(
+            "Undo",
+            Box::new(Undo),
+            !editable || !self.history.can_undo(),
+        )
+        .menu_with_disabled(
+            "Redo",
+            Box::new(Redo),
+            !editable || !self.history.can_redo(),
+        )
+        .separator()
+        .menu_with_disabled("Cut", Box::new(Cut), !editable || !has_selection)
+        .menu_with_disabled("Copy", Box::new(Copy), !has_selection)
+        .menu_with_disabled("Paste", Box::new(Paste), !editable || !has_clipboard)
+        .separator()
+        .menu_with_disabled("Select All", Box::new(SelectAll), self.text.is_empty())
+    }
+
     fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut ViewContext<Self>) {
         self.is_selecting = false;
     }
@@ -403,6 +829,26 @@ impl TextInput {
         }
     }
 
+    fn on_scroll_wheel(&mut self, event: &ScrollWheelEvent, cx: &mut ViewContext<Self>) {
+        let Some(bounds) = self.last_bounds.as_ref() else {
+            return;
+        };
+
+        let delta = event.delta.pixel_delta(self.last_line_height);
+        let max_offset = (self.scroll_size.height - bounds.size.height).max(px(0.));
+        let old_offset = self.scroll_handle.offset();
+        let offset = point(
+            old_offset.x,
+            (old_offset.y + delta.y).min(px(0.)).max(-max_offset),
+        );
+
+        if offset != old_offset {
+            self.scroll_handle.set_offset(offset);
+            cx.stop_propagation();
+            cx.notify();
+        }
+    }
+
     fn show_character_palette(&mut self, _: &ShowCharacterPalette, cx: &mut ViewContext<Self>) {
         cx.show_character_palette();
     }
@@ -428,7 +874,11 @@ impl TextInput {
 
     fn paste(&mut self, _: &Paste, cx: &mut ViewContext<Self>) {
         if let Some(clipboard) = cx.read_from_clipboard() {
-            let new_text = clipboard.text().replace('\n', "");
+            let new_text = if self.multi_line {
+                clipboard.text().clone()
+            } else {
+                clipboard.text().replace('\n', "")
+            };
             self.replace_text_in_range(None, &new_text, cx);
         }
     }
@@ -444,17 +894,30 @@ impl TextInput {
 
         let new_range = range.start..range.start + new_text.len();
 
-        self.history
-            .push(range.clone(), &old_text, new_range, new_text);
+        self.history.push(
+            range.clone(),
+            &old_text,
+            new_range,
+            new_text,
+            self.selected_range.clone(),
+        );
     }
 
     fn undo(&mut self, _: &Undo, cx: &mut ViewContext<Self>) {
         self.history.ignore = true;
         if let Some(changes) = self.history.undo() {
-            for change in changes {
+            for change in changes.iter() {
                 let range_utf16 = self.range_to_utf16(&change.new_range);
                 self.replace_text_in_range(Some(range_utf16), &change.old_text, cx);
             }
+
+            // Restore the selection before the first change of the group.
+            if let Some(change) = changes.last() {
+                if change.old_selection.end <= self.text.len() {
+                    self.selected_range = change.old_selection.clone();
+                    self.selection_reversed = false;
+                }
+            }
         }
         self.history.ignore = false;
     }
@@ -490,6 +953,17 @@ impl TextInput {
             return 0;
         }
 
+        if self.multi_line {
+            let (Some(bounds), Some(lines)) = (self.last_bounds.as_ref(), self.last_lines.as_ref())
+            else {
+                return 0;
+            };
+
+            let offset =
+                offset_for_position(lines, position - bounds.origin, self.last_line_height);
+            return offset.min(self.text.len());
+        }
+
         let (Some(bounds), Some(line)) = (self.last_bounds.as_ref(), self.last_layout.as_ref())
         else {
             return 0;
@@ -518,10 +992,6 @@ impl TextInput {
 
     /// Select the word at the given offset.
     fn select_word(&mut self, offset: usize, cx: &mut ViewContext<Self>) {
-        fn is_word(c: char) -> bool {
-            c.is_alphanumeric() || matches!(c, '_')
-        }
-
         let mut start = self.offset_to_utf16(offset);
         let mut end = start;
         let prev_text = self.text_for_range(0..start, cx).unwrap_or_default();
@@ -533,7 +1003,7 @@ impl TextInput {
         let next_chars = next_text.chars().peekable();
 
         for (_, c) in prev_chars.enumerate() {
-            if !is_word(c) {
+            if !is_word_char(c) {
                 break;
             }
 
@@ -541,7 +1011,7 @@ impl TextInput {
         }
 
         for (_, c) in next_chars.enumerate() {
-            if !is_word(c) {
+            if !is_word_char(c) {
                 break;
             }
 
@@ -552,6 +1022,60 @@ impl TextInput {
         cx.notify()
     }
 
+    /// Returns the offset of the same x position in the previous (`delta < 0`) or next visual row.
+    ///
+    /// If there is no such row, returns the start or the end of the text.
+    fn offset_for_row_delta(&self, offset: usize, delta: isize) -> usize {
+        let line_height = self.last_line_height;
+        let Some(lines) = self.last_lines.as_ref() else {
+            return offset;
+        };
+        let Some(position) = position_for_offset(lines, offset, line_height) else {
+            return offset;
+        };
+
+        let y = position.y + line_height / 2. + line_height * delta as f32;
+        if y < px(0.) {
+            return 0;
+        }
+        if y >= self.scroll_size.height {
+            return self.text.len();
+        }
+
+        offset_for_position(lines, point(position.x, y), line_height).min(self.text.len())
+    }
+
+    /// Returns the offset of the start of the line that contains the given offset.
+    fn start_of_line(&self, offset: usize) -> usize {
+        self.text[..offset]
+            .rfind('\n')
+            .map(|ix| ix + 1)
+            .unwrap_or(0)
+    }
+
+    /// Returns the offset of the end of the line that contains the given offset.
+    fn end_of_line(&self, offset: usize) -> usize {
+        self.text[offset..]
+            .find('\n')
+            .map(|ix| offset + ix)
+            .unwrap_or(self.text.len())
+    }
+
+    /// Returns the start offset of the word before the given offset, skipping the non-word chars between them.
+    fn previous_word_start(&self, offset: usize) -> usize {
+        let text = &self.text[..offset];
+        let end = text.trim_end_matches(|c| !is_word_char(c)).len();
+        text[..end].trim_end_matches(is_word_char).len()
+    }
+
+    /// Returns the end offset of the word after the given offset, skipping the non-word chars between them.
+    fn next_word_end(&self, offset: usize) -> usize {
+        let text = &self.text[offset..];
+        let start = text.len() - text.trim_start_matches(|c| !is_word_char(c)).len();
+        let word = &text[start..];
+        offset + start + word.len() - word.trim_start_matches(is_word_char).len()
+    }
+
     fn unselect(&mut self, cx: &mut ViewContext<Self>) {
         self.selected_range = self.cursor_offset()..self.cursor_offset();
         cx.notify()
@@ -622,8 +1146,16 @@ impl TextInput {
         cx.emit(InputEvent::Focus);
     }
 
-    fn on_blur(&mut self, cx: &mut ViewContext<Self>) {
+    fn on_blur(&mut self, _: FocusOutEvent, cx: &mut ViewContext<Self>) {
         self.unselect(cx);
+        if let Some(text) = self
+            .mask_pattern
+            .as_ref()
+            .and_then(|mask_pattern| mask_pattern.pad_fraction(&self.text))
+        {
+            let range = self.range_to_utf16(&(0..self.text.len()));
+            self.replace_text_in_range(Some(range), &text, cx);
+        }
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.stop(cx);
         });
@@ -640,6 +1172,41 @@ impl TextInput {
         self.pause_blink_cursor(cx)
     }
 
+    fn run_validation(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(validation) = &self.validation else {
+            return;
+        };
+
+        match validation {
+            Validation::Sync(validate) => {
+                let result = validate(&self.text);
+                self.set_validation_result(result, cx);
+            }
+            Validation::Async(validate) => {
+                let task = validate(&self.text, cx);
+                self.validating = true;
+                self._validation_task = cx.spawn(|view, mut cx| async move {
+                    let result = task.await;
+                    _ = view.update(&mut cx, |view, cx| {
+                        view.validating = false;
+                        view.set_validation_result(result, cx);
+                    });
+                });
+                cx.notify();
+            }
+        }
+    }
+
+    fn set_validation_result(
+        &mut self,
+        result: Result<(), SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.validation_error = result.clone().err();
+        cx.emit(InputEvent::Validate(result));
+        cx.notify();
+    }
+
     fn is_valid_input(&self, new_text: &str) -> bool {
         if new_text.is_empty() {
             return true;
@@ -705,17 +1272,43 @@ impl ViewInputHandler for TextInput {
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
-        let pending_text: SharedString =
+        let mut pending_text: SharedString =
             (self.text[0..range.start].to_owned() + new_text + &self.text[range.end..]).into();
+        let mut range = range;
+        let mut new_text = new_text.to_string();
+        let mut new_offset = range.start + new_text.len();
+
+        // Format the whole text with the mask pattern, and keep the cursor after the same raw char.
+        if let Some(mask_pattern) = &self.mask_pattern {
+            let raw_count = mask_pattern
+                .unmask(&pending_text[..new_offset])
+                .chars()
+                .count();
+            let masked = mask_pattern.mask(&mask_pattern.unmask(&pending_text));
+            new_offset = mask_pattern.offset_after_raw_chars(&masked, raw_count);
+
+            if masked == self.text.as_ref() {
+                self.marked_range.take();
+                self.move_to(new_offset, cx);
+                return;
+            }
+
+            range = 0..self.text.len();
+            new_text = masked;
+            pending_text = new_text.clone().into();
+        }
+
         if !self.is_valid_input(&pending_text) {
             return;
         }
 
-        self.push_history(&range, new_text, cx);
+        self.push_history(&range, &new_text, cx);
         self.text = pending_text;
-        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
+        self.selected_range = new_offset..new_offset;
         self.marked_range.take();
+        self.highlights.clear();
         cx.emit(InputEvent::Change(self.text.clone()));
+        self.run_validation(cx);
         cx.notify();
     }
 
@@ -749,7 +1342,9 @@ impl ViewInputHandler for TextInput {
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .map(|new_range| new_range.start + range.start..new_range.end + range.end)
             .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
+        self.highlights.clear();
         cx.emit(InputEvent::Change(self.text.clone()));
+        self.run_validation(cx);
         cx.notify();
     }
 
@@ -759,8 +1354,19 @@ impl ViewInputHandler for TextInput {
         bounds: Bounds<Pixels>,
         _cx: &mut ViewContext<Self>,
     ) -> Option<Bounds<Pixels>> {
-        let last_layout = self.last_layout.as_ref()?;
         let range = self.range_from_utf16(&range_utf16);
+        if self.multi_line {
+            let lines = self.last_lines.as_ref()?;
+            let line_height = self.last_line_height;
+            let start = position_for_offset(lines, range.start, line_height)?;
+            let end = position_for_offset(lines, range.end, line_height)?;
+            return Some(Bounds::from_corners(
+                bounds.origin + start,
+                bounds.origin + point(end.x, end.y + line_height),
+            ));
+        }
+
+        let last_layout = self.last_layout.as_ref()?;
         Some(Bounds::from_corners(
             point(
                 bounds.left() + last_layout.x_for_index(range.start),
@@ -780,15 +1386,122 @@ impl FocusableView for TextInput {
     }
 }
 
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_')
+}
+
+/// Returns the position of the given offset relative to the origin of the wrapped lines.
+fn position_for_offset(
+    lines: &[WrappedLine],
+    offset: usize,
+    line_height: Pixels,
+) -> Option<Point<Pixels>> {
+    let mut line_start = 0;
+    let mut y = px(0.);
+    for line in lines {
+        let line_end = line_start + line.len();
+        if offset <= line_end {
+            return line
+                .position_for_index(offset - line_start, line_height)
+                .map(|pos| point(pos.x, pos.y + y));
+        }
+
+        // The `+ 1` is for the `\n` between lines.
+        line_start = line_end + 1;
+        y += line.size(line_height).height;
+    }
+
+    None
+}
+
+/// Returns the bounds of the rows covered by the range, relative to the origin of the wrapped lines.
+fn range_bounds(
+    lines: &[WrappedLine],
+    range: &Range<usize>,
+    width: Pixels,
+    line_height: Pixels,
+) -> Vec<Bounds<Pixels>> {
+    let start = position_for_offset(lines, range.start, line_height);
+    let end = position_for_offset(lines, range.end, line_height);
+    let (Some(start), Some(end)) = (start, end) else {
+        return vec![];
+    };
+
+    if start.y == end.y {
+        return vec![Bounds::from_corners(
+            start,
+            point(end.x, end.y + line_height),
+        )];
+    }
+
+    let mut bounds = vec![];
+    // The first row, from the range start to the right edge.
+    bounds.push(Bounds::from_corners(
+        start,
+        point(width, start.y + line_height),
+    ));
+    // The full rows between the first and the last row.
+    if end.y > start.y + line_height {
+        bounds.push(Bounds::from_corners(
+            point(px(0.), start.y + line_height),
+            point(width, end.y),
+        ));
+    }
+    // The last row, from the left edge to the range end.
+    bounds.push(Bounds::from_corners(
+        point(px(0.), end.y),
+        point(end.x, end.y + line_height),
+    ));
+    bounds
+}
+
+/// Returns the offset closest to the position relative to the origin of the wrapped lines.
+fn offset_for_position(
+    lines: &[WrappedLine],
+    position: Point<Pixels>,
+    line_height: Pixels,
+) -> usize {
+    let mut line_start = 0;
+    let mut y = px(0.);
+    for (ix, line) in lines.iter().enumerate() {
+        let height = line.size(line_height).height;
+        if position.y < y + height || ix == lines.len() - 1 {
+            let local_position = point(
+                position.x.max(px(0.)),
+                (position.y - y).max(px(0.)).min(height - px(1.)),
+            );
+            let index = line
+                .index_for_position(local_position, line_height)
+                .unwrap_or(line.len());
+            return line_start + index;
+        }
+
+        line_start += line.len() + 1;
+        y += height;
+    }
+
+    line_start
+}
+
 struct TextElement {
     input: View<TextInput>,
 }
+
+enum TextLayout {
+    Line(ShapedLine),
+    Lines(SmallVec<[WrappedLine; 1]>),
+}
+
 struct PrepaintState {
     scroll_offset: Point<Pixels>,
-    line: Option<ShapedLine>,
+    layout: Option<TextLayout>,
+    line_height: Pixels,
     cursor: Option<PaintQuad>,
-    selection: Option<PaintQuad>,
+    selections: Vec<PaintQuad>,
+    /// The highlighted ranges background, painted under the selections.
+    highlights: Vec<PaintQuad>,
     bounds: Bounds<Pixels>,
+    scroll_size: gpui::Size<Pixels>,
 }
 
 impl IntoElement for TextElement {
@@ -799,6 +1512,109 @@ impl IntoElement for TextElement {
     }
 }
 
+impl TextElement {
+    /// Layout the text with wrapping, and scroll vertically to keep the cursor in view.
+    fn prepaint_lines(
+        &mut self,
+        display_text: SharedString,
+        runs: &[TextRun],
+        bounds: Bounds<Pixels>,
+        cx: &mut WindowContext,
+    ) -> PrepaintState {
+        let input = self.input.read(cx);
+        let selected_range = input.selected_range.clone();
+        let cursor = input.cursor_offset();
+        let style = cx.text_style();
+        let line_height = cx.line_height();
+        let font_size = style.font_size.to_pixels(cx.rem_size());
+
+        let lines = cx
+            .text_system()
+            .shape_text(display_text, font_size, runs, Some(bounds.size.width))
+            .unwrap();
+
+        let total_height = lines
+            .iter()
+            .map(|line| line.size(line_height).height)
+            .fold(px(0.), |sum, height| sum + height);
+        let max_offset = (total_height - bounds.size.height).max(px(0.));
+
+        let cursor_pos =
+            position_for_offset(&lines, cursor, line_height).unwrap_or(point(px(0.), px(0.)));
+
+        // Calculate the scroll offset to keep the cursor in view, only if the cursor has moved,
+        // so that the mouse wheel and scrollbar are still able to scroll away from the cursor.
+        let mut scroll_offset = input.scroll_handle.offset();
+        scroll_offset.x = px(0.);
+        if input.last_cursor_offset != Some(cursor) {
+            if scroll_offset.y + cursor_pos.y < px(0.) {
+                // cursor is out of top
+                scroll_offset.y = -cursor_pos.y;
+            } else if scroll_offset.y + cursor_pos.y + line_height > bounds.size.height {
+                // cursor is out of bottom
+                scroll_offset.y = bounds.size.height - cursor_pos.y - line_height;
+            }
+        }
+        scroll_offset.y = scroll_offset.y.min(px(0.)).max(-max_offset);
+
+        let mut text_bounds = bounds;
+        text_bounds.origin = bounds.origin + scroll_offset;
+        let origin = text_bounds.origin;
+
+        let inset = px(0.5);
+        let mut selections = vec![];
+        let mut cursor_quad = None;
+        if selected_range.is_empty() {
+            if input.show_cursor(cx) {
+                cursor_quad = Some(fill(
+                    Bounds::new(
+                        point(origin.x + cursor_pos.x, origin.y + cursor_pos.y + inset),
+                        size(px(1.5), line_height - inset * 2),
+                    ),
+                    crate::blue_500(),
+                ));
+            }
+        } else {
+            let selection_bg = cx.theme().selection;
+            selections.extend(
+                range_bounds(&lines, &selected_range, bounds.size.width, line_height)
+                    .into_iter()
+                    .map(|range_bounds| {
+                        fill(
+                            Bounds::new(range_bounds.origin + origin, range_bounds.size),
+                            selection_bg,
+                        )
+                    }),
+            );
+        }
+
+        let highlights = input
+            .visible_highlights()
+            .flat_map(|(range, color)| {
+                range_bounds(&lines, range, bounds.size.width, line_height)
+                    .into_iter()
+                    .map(move |range_bounds| {
+                        fill(
+                            Bounds::new(range_bounds.origin + origin, range_bounds.size),
+                            *color,
+                        )
+                    })
+            })
+            .collect();
+
+        PrepaintState {
+            scroll_offset,
+            bounds: text_bounds,
+            layout: Some(TextLayout::Lines(lines)),
+            line_height,
+            cursor: cursor_quad,
+            selections,
+            highlights,
+            scroll_size: size(bounds.size.width, total_height),
+        }
+    }
+}
+
 impl Element for TextElement {
     type RequestLayoutState = ();
     type PrepaintState = PrepaintState;
@@ -812,9 +1628,12 @@ impl Element for TextElement {
         _id: Option<&GlobalElementId>,
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
+        let input = self.input.read(cx);
+        let rows = if input.multi_line { input.rows } else { 1 };
+
         let mut style = Style::default();
         style.size.width = relative(1.).into();
-        style.size.height = cx.line_height().into();
+        style.size.height = (cx.line_height() * rows as f32).into();
         (cx.request_layout(style, []), ())
     }
 
@@ -879,6 +1698,10 @@ impl Element for TextElement {
             vec![run]
         };
 
+        if input.multi_line {
+            return self.prepaint_lines(display_text, &runs, bounds, cx);
+        }
+
         let font_size = style.font_size.to_pixels(cx.rem_size());
         let line = cx
             .text_system()
@@ -950,12 +1773,28 @@ impl Element for TextElement {
             )
         };
 
+        let highlights = input
+            .visible_highlights()
+            .map(|(range, color)| {
+                fill(
+                    Bounds::from_corners(
+                        point(bounds.left() + line.x_for_index(range.start), bounds.top()),
+                        point(bounds.left() + line.x_for_index(range.end), bounds.bottom()),
+                    ),
+                    *color,
+                )
+            })
+            .collect();
+
         PrepaintState {
             scroll_offset,
             bounds,
-            line: Some(line),
+            layout: Some(TextLayout::Line(line)),
+            line_height: cx.line_height(),
             cursor,
-            selection,
+            selections: selection.into_iter().collect(),
+            highlights,
+            scroll_size: bounds.size,
         }
     }
 
@@ -970,26 +1809,53 @@ impl Element for TextElement {
         let focus_handle = self.input.read(cx).focus_handle.clone();
         let focused = focus_handle.is_focused(cx);
         let bounds = prepaint.bounds;
+        let line_height = prepaint.line_height;
 
         cx.handle_input(
             &focus_handle,
             ElementInputHandler::new(bounds, self.input.clone()),
         );
-        if let Some(selection) = prepaint.selection.take() {
+        for highlight in prepaint.highlights.drain(..) {
+            cx.paint_quad(highlight)
+        }
+        for selection in prepaint.selections.drain(..) {
             cx.paint_quad(selection)
         }
-        let line = prepaint.line.take().unwrap();
-        line.paint(bounds.origin, cx.line_height(), cx).unwrap();
+        let layout = prepaint.layout.take().unwrap();
+        match &layout {
+            TextLayout::Line(line) => {
+                line.paint(bounds.origin, line_height, cx).unwrap();
+            }
+            TextLayout::Lines(lines) => {
+                let mut origin = bounds.origin;
+                for line in lines.iter() {
+                    line.paint(origin, line_height, cx).unwrap();
+                    origin.y += line.size(line_height).height;
+                }
+            }
+        }
 
         if focused {
             if let Some(cursor) = prepaint.cursor.take() {
                 cx.paint_quad(cursor);
             }
         }
+        let cursor = self.input.read(cx).cursor_offset();
         self.input.update(cx, |input, _cx| {
-            input.scroll_offset = prepaint.scroll_offset;
-            input.last_layout = Some(line);
+            match layout {
+                TextLayout::Line(line) => {
+                    input.scroll_offset = prepaint.scroll_offset;
+                    input.last_layout = Some(line);
+                }
+                TextLayout::Lines(lines) => {
+                    input.scroll_handle.set_offset(prepaint.scroll_offset);
+                    input.last_lines = Some(lines);
+                }
+            }
+            input.last_line_height = line_height;
+            input.last_cursor_offset = Some(cursor);
             input.last_bounds = Some(bounds);
+            input.scroll_size = prepaint.scroll_size;
         });
     }
 }
@@ -1001,19 +1867,41 @@ impl Render for TextInput {
         let prefix = self.prefix.as_ref().map(|build| build(cx));
         let suffix = self.suffix.as_ref().map(|build| build(cx));
 
-        div()
+        let mut key_context = KeyContext::default();
+        key_context.add(CONTEXT);
+        if self.multi_line {
+            key_context.add(MULTI_LINE);
+        }
+
+        let loading = self.loading || self.validating;
+
+        let input = div()
             .flex()
-            .key_context(CONTEXT)
+            .key_context(key_context)
             .track_focus(&self.focus_handle)
             .when(!self.disabled, |this| {
                 this.on_action(cx.listener(Self::backspace))
                     .on_action(cx.listener(Self::delete))
+                    .on_action(cx.listener(Self::delete_to_previous_word_start))
+                    .on_action(cx.listener(Self::delete_to_next_word_end))
+                    .on_action(cx.listener(Self::delete_to_beginning_of_line))
                     .on_action(cx.listener(Self::enter))
             })
             .on_action(cx.listener(Self::left))
             .on_action(cx.listener(Self::right))
             .on_action(cx.listener(Self::select_left))
             .on_action(cx.listener(Self::select_right))
+            .on_action(cx.listener(Self::move_to_previous_word))
+            .on_action(cx.listener(Self::move_to_next_word))
+            .on_action(cx.listener(Self::select_to_previous_word))
+            .on_action(cx.listener(Self::select_to_next_word))
+            .when(self.multi_line, |this| {
+                this.on_action(cx.listener(Self::up))
+                    .on_action(cx.listener(Self::down))
+                    .on_action(cx.listener(Self::select_up))
+                    .on_action(cx.listener(Self::select_down))
+                    .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            })
             .on_action(cx.listener(Self::select_all))
             .on_action(cx.listener(Self::select_to_home))
             .on_action(cx.listener(Self::select_to_end))
@@ -1035,11 +1923,30 @@ impl Render for TextInput {
             .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .capture_any_mouse_down(cx.listener(Self::on_secondary_mouse_down))
+            .context_menu({
+                let view = cx.view().clone();
+                move |menu, cx| {
+                    let input = view.read(cx);
+                    let append = input.context_menu.clone();
+                    let menu = input.build_context_menu(menu, cx);
+                    match append {
+                        Some(append) => append(menu.separator(), cx),
+                        None => menu,
+                    }
+                }
+            })
             .size_full()
             .line_height(rems(1.25))
             .text_size(rems(0.875))
             .input_py(self.size)
-            .input_h(self.size)
+            .map(|this| {
+                if self.multi_line {
+                    this.input_text_size(self.size).h_auto()
+                } else {
+                    this.input_h(self.size)
+                }
+            })
             .when(self.appearance, |this| {
                 this.bg(if self.disabled {
                     cx.theme().muted
@@ -1051,12 +1958,21 @@ impl Render for TextInput {
                 .rounded(px(cx.theme().radius))
                 .shadow_sm()
                 .when(focused, |this| this.outline(cx))
+                .when(self.validation_error.is_some(), |this| {
+                    this.border_color(cx.theme().destructive)
+                })
                 .when(prefix.is_none(), |this| this.input_pl(self.size))
                 .when(suffix.is_none(), |this| this.input_pr(self.size))
             })
             .children(prefix)
             .gap_1()
-            .items_center()
+            .map(|this| {
+                if self.multi_line {
+                    this.items_start()
+                } else {
+                    this.items_center()
+                }
+            })
             .child(
                 div()
                     .id("TextElement")
@@ -1065,13 +1981,42 @@ impl Render for TextInput {
                     .cursor_text()
                     .child(TextElement {
                         input: cx.view().clone(),
+                    })
+                    .when(self.multi_line, |this| {
+                        this.relative()
+                            .overflow_y_hidden()
+                            .child(Scrollbar::vertical(
+                                cx.view().clone(),
+                                self.scrollbar_state.clone(),
+                                self.scroll_handle.clone(),
+                                self.scroll_size,
+                            ))
                     }),
             )
-            .when(self.loading, |this| this.child(Indicator::new()))
+            .when(loading, |this| this.child(Indicator::new()))
             .when(
-                self.cleanable && !self.loading && !self.text.is_empty(),
+                self.cleanable && !loading && !self.text.is_empty(),
                 |this| this.child(ClearButton::new(cx).on_click(cx.listener(Self::clean))),
             )
-            .children(suffix)
+            .children(suffix);
+
+        // Keep the input as the root element, unless the validation is set to show the error below.
+        if self.validation.is_none() {
+            return input.into_any_element();
+        }
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .child(input)
+            .when_some(self.validation_error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().destructive)
+                        .child(error),
+                )
+            })
+            .into_any_element()
     }
 }
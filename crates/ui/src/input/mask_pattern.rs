@@ -0,0 +1,295 @@
+use gpui::SharedString;
+
+/// The mask pattern of the `TextInput`, to format the text as the user types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaskPattern {
+    /// Format the text by a pattern, e.g.: `(999) 999-9999`.
+    ///
+    /// - `9` matches a digit.
+    /// - `A` matches a letter.
+    /// - `*` matches a digit or a letter.
+    ///
+    /// Other chars are the literal separators, they are inserted when the user types the next char.
+    Pattern(SharedString),
+    /// Format the text as a number, with the thousands `separator` and the max `fraction` digits.
+    Number {
+        separator: Option<char>,
+        fraction: Option<usize>,
+    },
+}
+
+impl MaskPattern {
+    /// Create a pattern mask, e.g.: `(999) 999-9999`.
+    pub fn new(pattern: impl Into<SharedString>) -> Self {
+        Self::Pattern(pattern.into())
+    }
+
+    /// Create a number mask with the `,` thousands separator and the max `fraction` digits.
+    pub fn number(fraction: Option<usize>) -> Self {
+        Self::Number {
+            separator: Some(','),
+            fraction,
+        }
+    }
+
+    fn is_placeholder(c: char) -> bool {
+        matches!(c, '9' | 'A' | '*')
+    }
+
+    fn is_match(placeholder: char, c: char) -> bool {
+        match placeholder {
+            '9' => c.is_ascii_digit(),
+            'A' => c.is_ascii_alphabetic(),
+            '*' => c.is_ascii_alphanumeric(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the char at the given char index of the formatted text is a literal separator.
+    fn is_literal(&self, ix: usize, c: char) -> bool {
+        match self {
+            Self::Pattern(pattern) => pattern
+                .chars()
+                .nth(ix)
+                .map_or(true, |p| !Self::is_placeholder(p)),
+            Self::Number { separator, .. } => Some(c) == *separator,
+        }
+    }
+
+    /// Remove the literal separators and the invalid chars, returns the raw value.
+    ///
+    /// The pattern is unmasked by position, so the literals like `1` in `+1 (999)` are not a part of the raw value.
+    pub fn unmask(&self, text: &str) -> String {
+        match self {
+            Self::Pattern(pattern) => {
+                let pattern: Vec<char> = pattern.chars().collect();
+                let mut raw = String::new();
+                let mut pos = 0;
+
+                for c in text.chars() {
+                    if pattern
+                        .get(pos)
+                        .is_some_and(|p| *p == c && !Self::is_placeholder(*p))
+                    {
+                        pos += 1;
+                        continue;
+                    }
+
+                    // Skip the literals missing in the text, e.g.: a raw value is pasted or a separator is deleted.
+                    while pattern.get(pos).is_some_and(|p| !Self::is_placeholder(*p)) {
+                        pos += 1;
+                    }
+
+                    // The chars not matching the placeholder or beyond the pattern are dropped.
+                    if pattern.get(pos).is_some_and(|p| Self::is_match(*p, c)) {
+                        raw.push(c);
+                        pos += 1;
+                    }
+                }
+
+                raw
+            }
+            Self::Number { .. } => {
+                let mut has_dot = false;
+                text.chars()
+                    .enumerate()
+                    .filter(|(ix, c)| match c {
+                        '0'..='9' => true,
+                        '-' => *ix == 0,
+                        '.' if !has_dot => {
+                            has_dot = true;
+                            true
+                        }
+                        _ => false,
+                    })
+                    .map(|(_, c)| c)
+                    .collect()
+            }
+        }
+    }
+
+    /// Format the raw value with the mask pattern.
+    pub fn mask(&self, raw: &str) -> String {
+        match self {
+            Self::Pattern(pattern) => {
+                let mut result = String::new();
+                let mut chars = raw.chars().peekable();
+
+                for p in pattern.chars() {
+                    if chars.peek().is_none() {
+                        break;
+                    }
+
+                    if !Self::is_placeholder(p) {
+                        result.push(p);
+                        continue;
+                    }
+
+                    // Skip the chars that don't match the placeholder.
+                    match chars.by_ref().find(|c| Self::is_match(p, *c)) {
+                        Some(c) => result.push(c),
+                        None => break,
+                    }
+                }
+
+                // Remove the trailing separators, if the rest chars are all invalid.
+                while result
+                    .chars()
+                    .last()
+                    .is_some_and(|c| self.is_literal(result.chars().count() - 1, c))
+                {
+                    result.pop();
+                }
+
+                result
+            }
+            Self::Number {
+                separator,
+                fraction,
+            } => {
+                let raw = self.unmask(raw);
+                let (sign, raw) = match raw.strip_prefix('-') {
+                    Some(raw) => ("-", raw),
+                    None => ("", raw.as_str()),
+                };
+                let (integer, decimals) = match raw.split_once('.') {
+                    Some((integer, decimals)) => (integer, Some(decimals)),
+                    None => (raw, None),
+                };
+
+                let mut result = sign.to_string();
+                for (ix, c) in integer.chars().enumerate() {
+                    if ix > 0 && (integer.len() - ix) % 3 == 0 {
+                        if let Some(separator) = separator {
+                            result.push(*separator);
+                        }
+                    }
+                    result.push(c);
+                }
+
+                if let Some(decimals) = decimals {
+                    if *fraction != Some(0) {
+                        result.push('.');
+                        result.extend(decimals.chars().take(fraction.unwrap_or(usize::MAX)));
+                    }
+                }
+
+                result
+            }
+        }
+    }
+
+    /// Pad the decimals of the formatted number to the `fraction` digits, e.g.: `1,234.5` to `1,234.50`.
+    pub(super) fn pad_fraction(&self, text: &str) -> Option<String> {
+        let Self::Number {
+            fraction: Some(fraction),
+            ..
+        } = self
+        else {
+            return None;
+        };
+        if *fraction == 0 || text.is_empty() || text == "-" {
+            return None;
+        }
+
+        let decimals = text.split_once('.').map_or(0, |(_, d)| d.len());
+        if decimals >= *fraction {
+            return None;
+        }
+
+        let mut result = text.to_string();
+        if decimals == 0 && !text.contains('.') {
+            result.push('.');
+        }
+        result.push_str(&"0".repeat(fraction - decimals));
+        Some(result)
+    }
+
+    /// Returns the byte offset in the formatted `text` after the `raw_count` raw chars.
+    pub(super) fn offset_after_raw_chars(&self, text: &str, raw_count: usize) -> usize {
+        if raw_count == 0 {
+            return 0;
+        }
+
+        let mut count = 0;
+        for (ix, (offset, c)) in text.char_indices().enumerate() {
+            if !self.is_literal(ix, c) {
+                count += 1;
+                if count == raw_count {
+                    return offset + c.len_utf8();
+                }
+            }
+        }
+
+        text.len()
+    }
+}
+
+impl From<&'static str> for MaskPattern {
+    fn from(pattern: &'static str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaskPattern;
+
+    #[test]
+    fn test_pattern_mask() {
+        let mask = MaskPattern::new("(999) 999-9999");
+        assert_eq!(mask.mask(""), "");
+        assert_eq!(mask.mask("5"), "(5");
+        assert_eq!(mask.mask("555"), "(555");
+        assert_eq!(mask.mask("5551"), "(555) 1");
+        assert_eq!(mask.mask("5551234567"), "(555) 123-4567");
+        assert_eq!(mask.mask("555123456789"), "(555) 123-4567");
+        assert_eq!(mask.mask("555a1"), "(555) 1");
+        assert_eq!(mask.unmask("(555) 123-4567"), "5551234567");
+
+        // The literals with digits are not a part of the raw value.
+        let mask = MaskPattern::new("+1 (999) 999-9999");
+        assert_eq!(mask.mask("5"), "+1 (5");
+        assert_eq!(mask.unmask("+1 (5"), "5");
+        assert_eq!(mask.unmask("5551234567"), "5551234567");
+        assert_eq!(mask.unmask("+1 (555 1"), "5551");
+        let masked = mask.mask("5551234567");
+        assert_eq!(masked, "+1 (555) 123-4567");
+        assert_eq!(mask.mask(&mask.unmask(&masked)), masked);
+
+        let mask = MaskPattern::new("AAA-***");
+        assert_eq!(mask.mask("abc1d2"), "abc-1d2");
+        assert_eq!(mask.mask("ab1"), "ab");
+    }
+
+    #[test]
+    fn test_number_mask() {
+        let mask = MaskPattern::number(Some(2));
+        assert_eq!(mask.mask(""), "");
+        assert_eq!(mask.mask("123"), "123");
+        assert_eq!(mask.mask("1234"), "1,234");
+        assert_eq!(mask.mask("1234567.891"), "1,234,567.89");
+        assert_eq!(mask.mask("-1234.5"), "-1,234.5");
+        assert_eq!(mask.mask("1,2a34.5.6"), "1,234.56");
+        assert_eq!(mask.unmask("-1,234.56"), "-1234.56");
+
+        assert_eq!(mask.pad_fraction("1,234"), Some("1,234.00".into()));
+        assert_eq!(mask.pad_fraction("1,234.5"), Some("1,234.50".into()));
+        assert_eq!(mask.pad_fraction("1,234.56"), None);
+
+        let mask = MaskPattern::number(Some(0));
+        assert_eq!(mask.mask("1234.5"), "1,234");
+    }
+
+    #[test]
+    fn test_offset_after_raw_chars() {
+        let mask = MaskPattern::new("(999) 999-9999");
+        assert_eq!(mask.offset_after_raw_chars("(555) 123", 0), 0);
+        assert_eq!(mask.offset_after_raw_chars("(555) 123", 3), 4);
+        assert_eq!(mask.offset_after_raw_chars("(555) 123", 4), 7);
+        assert_eq!(mask.offset_after_raw_chars("(555) 123", 10), 9);
+
+        let mask = MaskPattern::number(None);
+        assert_eq!(mask.offset_after_raw_chars("1,234", 2), 3);
+    }
+}
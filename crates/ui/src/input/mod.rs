@@ -1,9 +1,27 @@
+mod autocomplete;
 mod blink_cursor;
 mod clear_button;
 mod history;
 mod input;
+mod mask_pattern;
+mod number_input;
 mod otp_input;
+mod tag_input;
 
+use gpui::AppContext;
+
+pub use autocomplete::*;
 pub(crate) use clear_button::*;
+pub use history::HistorySnapshot;
 pub use input::*;
+pub use mask_pattern::*;
+pub use number_input::*;
 pub use otp_input::*;
+pub use tag_input::*;
+
+pub fn init(cx: &mut AppContext) {
+    input::init(cx);
+    autocomplete::init(cx);
+    number_input::init(cx);
+    otp_input::init(cx);
+}
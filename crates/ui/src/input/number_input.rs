@@ -0,0 +1,291 @@
+use std::{fmt::Display, str::FromStr};
+
+use gpui::{
+    actions, div, px, AppContext, EventEmitter, FocusHandle, FocusableView,
+    InteractiveElement as _, IntoElement, KeyBinding, ParentElement as _, Pixels, Render,
+    ScrollWheelEvent, SharedString, Styled as _, Subscription, View, ViewContext,
+    VisualContext as _, WeakView, WindowContext,
+};
+
+use crate::{button::Button, h_flex, Clickable as _, IconName, Sizable as _};
+
+use super::{InputEvent, TextInput};
+
+actions!(
+    number_input,
+    [Increment, Decrement, IncrementPage, DecrementPage]
+);
+
+const CONTEXT: &str = "NumberInput";
+/// The number of steps to change by PageUp and PageDown.
+const PAGE_STEPS: i32 = 10;
+/// The scroll distance of one step when scrolling the wheel.
+const SCROLL_STEP: f32 = 20.;
+
+pub fn init(cx: &mut AppContext) {
+    cx.bind_keys([
+        KeyBinding::new("up", Increment, Some(CONTEXT)),
+        KeyBinding::new("down", Decrement, Some(CONTEXT)),
+        KeyBinding::new("pageup", IncrementPage, Some(CONTEXT)),
+        KeyBinding::new("pagedown", DecrementPage, Some(CONTEXT)),
+    ]);
+}
+
+/// The value type of the [`NumberInput`], implemented for `f64` and `i64`.
+pub trait NumberValue: Copy + Default + PartialOrd + Display + FromStr + 'static {
+    /// The default step to increment or decrement.
+    const STEP: Self;
+    /// True if the value has no decimals, then the `.` can't be typed.
+    const INTEGER: bool;
+
+    /// Returns the value added by `step` for `times`, the `times` is negative to decrement.
+    fn add_steps(self, step: Self, times: i32) -> Self;
+}
+
+impl NumberValue for i64 {
+    const STEP: Self = 1;
+    const INTEGER: bool = true;
+
+    fn add_steps(self, step: Self, times: i32) -> Self {
+        self.saturating_add(step.saturating_mul(times as i64))
+    }
+}
+
+impl NumberValue for f64 {
+    const STEP: Self = 1.;
+    const INTEGER: bool = false;
+
+    fn add_steps(self, step: Self, times: i32) -> Self {
+        let decimals = |v: f64| v.to_string().split_once('.').map_or(0, |(_, d)| d.len());
+
+        // Round to the decimals of the value and step, to avoid the results like `0.30000000000000004`.
+        let factor = 10f64.powi(decimals(self).max(decimals(step)) as i32);
+        ((self + step * times as f64) * factor).round() / factor
+    }
+}
+
+#[derive(Clone)]
+pub enum NumberInputEvent<T> {
+    /// The value has changed, `None` if the input is empty or not a number.
+    Change(Option<T>),
+}
+
+/// A numeric input with stepper buttons, the value can be changed by the up/down keys and the scroll wheel.
+pub struct NumberInput<T: NumberValue = f64> {
+    input: View<TextInput>,
+    value: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+    /// The scroll wheel delta that not enough to change a step.
+    scroll_delta: Pixels,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<T: NumberValue> NumberInput<T> {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let view = cx.view().downgrade();
+        let pattern = if T::INTEGER {
+            r"^-?\d*$"
+        } else {
+            r"^-?\d*\.?\d*$"
+        };
+
+        let input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .pattern(regex::Regex::new(pattern).unwrap())
+                .suffix(move |cx| Self::render_stepper(view.clone(), cx))
+        });
+        let _subscriptions = vec![cx.subscribe(&input, Self::on_input_event)];
+
+        Self {
+            input,
+            value: None,
+            min: None,
+            max: None,
+            step: T::STEP,
+            scroll_delta: px(0.),
+            _subscriptions,
+        }
+    }
+
+    /// Set the min value, the value will be clamped when stepping or blurred.
+    pub fn min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Set the max value, the value will be clamped when stepping or blurred.
+    pub fn max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the step to increment or decrement, default is 1.
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Return the value of the number input, `None` if the input is empty or not a number.
+    pub fn value(&self) -> Option<T> {
+        self.value
+    }
+
+    /// Set the value of the number input, the value will be clamped by the min and max.
+    pub fn set_value(&mut self, value: Option<T>, cx: &mut ViewContext<Self>) {
+        let text = value
+            .map(|value| self.clamp(value).to_string())
+            .unwrap_or_default();
+        self.input.update(cx, |input, cx| input.set_text(text, cx));
+    }
+
+    /// Set the placeholder text of the input field.
+    pub fn set_placeholder(
+        &mut self,
+        placeholder: impl Into<SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.input
+            .update(cx, |input, _| input.set_placeholder(placeholder));
+        cx.notify();
+    }
+
+    /// Set the disabled state of the number input.
+    pub fn set_disabled(&mut self, disabled: bool, cx: &mut ViewContext<Self>) {
+        self.input
+            .update(cx, |input, cx| input.set_disabled(disabled, cx));
+    }
+
+    pub fn focus(&self, cx: &mut ViewContext<Self>) {
+        self.input.focus_handle(cx).focus(cx);
+    }
+
+    fn clamp(&self, mut value: T) -> T {
+        if let Some(min) = self.min {
+            if value < min {
+                value = min;
+            }
+        }
+        if let Some(max) = self.max {
+            if value > max {
+                value = max;
+            }
+        }
+        value
+    }
+
+    fn add_steps(&mut self, times: i32, cx: &mut ViewContext<Self>) {
+        if self.input.read(cx).disabled() {
+            return;
+        }
+
+        let value = match self.value {
+            Some(value) => value.add_steps(self.step, times),
+            None => T::default(),
+        };
+        self.set_value(Some(value), cx);
+    }
+
+    fn increment(&mut self, _: &Increment, cx: &mut ViewContext<Self>) {
+        self.add_steps(1, cx);
+    }
+
+    fn decrement(&mut self, _: &Decrement, cx: &mut ViewContext<Self>) {
+        self.add_steps(-1, cx);
+    }
+
+    fn increment_page(&mut self, _: &IncrementPage, cx: &mut ViewContext<Self>) {
+        self.add_steps(PAGE_STEPS, cx);
+    }
+
+    fn decrement_page(&mut self, _: &DecrementPage, cx: &mut ViewContext<Self>) {
+        self.add_steps(-PAGE_STEPS, cx);
+    }
+
+    fn on_scroll_wheel(&mut self, event: &ScrollWheelEvent, cx: &mut ViewContext<Self>) {
+        if !self.input.focus_handle(cx).is_focused(cx) {
+            return;
+        }
+        cx.stop_propagation();
+
+        self.scroll_delta = self.scroll_delta + event.delta.pixel_delta(px(SCROLL_STEP)).y;
+        let times = (self.scroll_delta / px(SCROLL_STEP)) as i32;
+        if times != 0 {
+            self.scroll_delta = self.scroll_delta - px(SCROLL_STEP) * times as f32;
+            self.add_steps(times, cx);
+        }
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: View<TextInput>,
+        event: &InputEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                let value = text.parse::<T>().ok();
+                if value != self.value {
+                    self.value = value;
+                    cx.emit(NumberInputEvent::Change(value));
+                }
+            }
+            InputEvent::Blur => {
+                // Clamp the typed value, and format it, e.g.: `-` to empty, `1.50` to `1.5`.
+                let value = self.value.map(|value| self.clamp(value));
+                let text = value.map(|value| value.to_string()).unwrap_or_default();
+                if text != self.input.read(cx).text().as_ref() {
+                    self.set_value(value, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render_stepper(view: WeakView<Self>, cx: &mut WindowContext) -> impl IntoElement {
+        let decrement_view = view.clone();
+
+        h_flex()
+            .mr_1()
+            .child(
+                Button::new("decrement", cx)
+                    .icon(IconName::Minus)
+                    .ghost()
+                    .xsmall()
+                    .on_click(move |_, cx| {
+                        _ = decrement_view.update(cx, |this, cx| this.add_steps(-1, cx));
+                    }),
+            )
+            .child(
+                Button::new("increment", cx)
+                    .icon(IconName::Plus)
+                    .ghost()
+                    .xsmall()
+                    .on_click(move |_, cx| {
+                        _ = view.update(cx, |this, cx| this.add_steps(1, cx));
+                    }),
+            )
+    }
+}
+
+impl<T: NumberValue> EventEmitter<NumberInputEvent<T>> for NumberInput<T> {}
+
+impl<T: NumberValue> FocusableView for NumberInput<T> {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl<T: NumberValue> Render for NumberInput<T> {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .key_context(CONTEXT)
+            .on_action(cx.listener(Self::increment))
+            .on_action(cx.listener(Self::decrement))
+            .on_action(cx.listener(Self::increment_page))
+            .on_action(cx.listener(Self::decrement_page))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .child(self.input.clone())
+    }
+}
@@ -1,12 +1,53 @@
 use gpui::{
-    div, prelude::FluentBuilder, px, AnyElement, Context, EventEmitter, FocusHandle, FocusableView,
-    InteractiveElement, IntoElement, KeyDownEvent, Model, MouseButton, MouseDownEvent,
-    ParentElement as _, Render, SharedString, Styled as _, ViewContext,
+    div, prelude::FluentBuilder, px, AnyElement, AppContext, Context, EventEmitter, FocusHandle,
+    FocusableView, InteractiveElement, IntoElement, KeyBinding, KeyDownEvent, Model, MouseButton,
+    ParentElement as _, Render, SharedString, Styled as _, Task, ViewContext, WindowContext,
 };
 
-use crate::{h_flex, theme::ActiveTheme, v_flex, Icon, IconName, Sizable, Size};
+use crate::{
+    h_flex, indicator::Indicator, theme::ActiveTheme, v_flex, Icon, IconName, Sizable, Size,
+};
+
+use super::{blink_cursor::BlinkCursor, InputEvent, Paste};
+
+const CONTEXT: &str = "OtpInput";
+
+pub fn init(cx: &mut AppContext) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-v", Paste, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-v", Paste, Some(CONTEXT)),
+    ]);
+}
+
+/// The chars that can be typed or pasted into the [`OtpInput`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OtpCharset {
+    /// Only the digits `0-9`, this is the default.
+    #[default]
+    Digits,
+    /// The ASCII letters and digits, the letters are converted to uppercase.
+    Alphanumeric,
+}
 
-use super::{blink_cursor::BlinkCursor, InputEvent};
+impl OtpCharset {
+    /// Returns the char to input, or `None` if the char is not allowed.
+    fn normalize(&self, c: char) -> Option<char> {
+        match self {
+            Self::Digits => c.is_ascii_digit().then_some(c),
+            Self::Alphanumeric => c.is_ascii_alphanumeric().then(|| c.to_ascii_uppercase()),
+        }
+    }
+}
+
+/// The state of the verification, it's started when all boxes are filled.
+enum VerifyState {
+    None,
+    Verifying,
+    Success,
+    Error(SharedString),
+}
 
 pub enum InputOptEvent {
     /// When all OTP input have filled, this event will be triggered.
@@ -21,6 +62,13 @@ pub struct OtpInput {
     value: SharedString,
     blink_cursor: Model<BlinkCursor>,
     size: Size,
+    /// The index of the focused box.
+    cursor: usize,
+    charset: OtpCharset,
+    verify:
+        Option<Box<dyn Fn(&str, &mut WindowContext) -> Task<Result<(), SharedString>> + 'static>>,
+    verify_state: VerifyState,
+    _verify_task: Task<()>,
 }
 
 impl OtpInput {
@@ -35,6 +83,11 @@ impl OtpInput {
             masked: false,
             blink_cursor: blink_cursor.clone(),
             size: Size::Medium,
+            cursor: 0,
+            charset: OtpCharset::default(),
+            verify: None,
+            verify_state: VerifyState::None,
+            _verify_task: Task::Ready(None),
         };
 
         // Observe the blink cursor to repaint the view when it changes.
@@ -67,15 +120,43 @@ impl OtpInput {
     /// Set default value of the OTP Input.
     pub fn default_value(mut self, value: impl Into<SharedString>) -> Self {
         self.value = value.into();
+        self.cursor = self.max_cursor();
         self
     }
 
     /// Set value of the OTP Input.
     pub fn set_value(&mut self, value: impl Into<SharedString>, cx: &mut ViewContext<Self>) {
         self.value = value.into();
+        self.cursor = self.max_cursor();
+        self.reset_verify();
         cx.notify();
     }
 
+    /// Set the chars can be typed or pasted, default is [`OtpCharset::Digits`].
+    pub fn charset(mut self, charset: OtpCharset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Set the async verification, it runs when all boxes are filled.
+    ///
+    /// The boxes show the success or error state by the result, and the `InputEvent::Validate` is emitted.
+    pub fn verify(
+        mut self,
+        f: impl Fn(&str, &mut WindowContext) -> Task<Result<(), SharedString>> + 'static,
+    ) -> Self {
+        self.verify = Some(Box::new(f));
+        self
+    }
+
+    /// Return the error message of the last verification.
+    pub fn verify_error(&self) -> Option<&SharedString> {
+        match &self.verify_state {
+            VerifyState::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+
     /// Return the value of the OTP Input.
     pub fn value(&self) -> SharedString {
         self.value.clone()
@@ -97,45 +178,144 @@ impl OtpInput {
         self.focus_handle.focus(cx);
     }
 
-    fn on_input_mouse_down(&mut self, _: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+    /// The last box can be focused, the first empty box or the last box when all are filled.
+    fn max_cursor(&self) -> usize {
+        self.value
+            .chars()
+            .count()
+            .min(self.length.saturating_sub(1))
+    }
+
+    fn on_input_mouse_down(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        self.cursor = ix.min(self.max_cursor());
         cx.focus(&self.focus_handle);
+        cx.notify();
     }
 
     fn on_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
         let mut chars: Vec<char> = self.value.chars().collect();
-        let ix = chars.len();
-
         let key = event.keystroke.key.as_str();
 
         match key {
             "backspace" => {
-                if ix > 0 {
-                    let ix = ix - 1;
-                    chars.remove(ix);
+                if self.cursor + 1 < chars.len() {
+                    // Don't clear the box in the middle, the later boxes will be shifted, type to replace it instead.
+                    return;
+                } else if self.cursor < chars.len() {
+                    // Clear the last filled box.
+                    chars.remove(self.cursor);
+                } else if self.cursor > 0 {
+                    // Move to the previous box and clear it.
+                    self.cursor -= 1;
+                    chars.remove(self.cursor);
+                } else {
+                    return;
                 }
             }
+            "left" => {
+                self.cursor = self.cursor.saturating_sub(1);
+                self.pause_blink_cursor(cx);
+                cx.notify();
+                return;
+            }
+            "right" => {
+                self.cursor = (self.cursor + 1).min(self.max_cursor());
+                self.pause_blink_cursor(cx);
+                cx.notify();
+                return;
+            }
             _ => {
-                let c = key.chars().next().unwrap();
-                if !matches!(c, '0'..='9') {
+                let modifiers = &event.keystroke.modifiers;
+                if modifiers.control || modifiers.platform || modifiers.alt {
                     return;
                 }
-                if ix >= self.length {
+
+                let mut key_chars = key.chars();
+                let (Some(c), None) = (key_chars.next(), key_chars.next()) else {
                     return;
-                }
+                };
+                let Some(c) = self.charset.normalize(c) else {
+                    return;
+                };
 
-                chars.push(c);
+                if self.cursor < chars.len() {
+                    chars[self.cursor] = c;
+                } else if chars.len() < self.length {
+                    chars.push(c);
+                } else {
+                    return;
+                }
+                self.cursor = (self.cursor + 1).min(self.length.saturating_sub(1));
             }
         }
 
+        self.update_value(chars, cx);
+    }
+
+    /// Fill the boxes by the pasted code from the focused box, or from the first box if it's a full code.
+    fn paste(&mut self, _: &Paste, cx: &mut ViewContext<Self>) {
+        let Some(clipboard) = cx.read_from_clipboard() else {
+            return;
+        };
+        let pasted: Vec<char> = clipboard
+            .text()
+            .chars()
+            .filter_map(|c| self.charset.normalize(c))
+            .collect();
+        if pasted.is_empty() {
+            return;
+        }
+
+        let start = if pasted.len() >= self.length {
+            0
+        } else {
+            self.cursor
+        };
+        let mut chars: Vec<char> = self.value.chars().take(start).collect();
+        chars.extend(pasted);
+        chars.truncate(self.length);
+
+        self.cursor = chars.len().min(self.length.saturating_sub(1));
+        self.update_value(chars, cx);
+    }
+
+    fn update_value(&mut self, chars: Vec<char>, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
         self.value = SharedString::from(chars.iter().collect::<String>());
+        self.reset_verify();
 
         if self.value.chars().count() == self.length {
             cx.emit(InputEvent::Change(self.value.clone()));
+            self.run_verify(cx);
         }
         cx.notify()
     }
 
+    fn reset_verify(&mut self) {
+        self.verify_state = VerifyState::None;
+        self._verify_task = Task::Ready(None);
+    }
+
+    fn run_verify(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(verify) = &self.verify else {
+            return;
+        };
+
+        let task = verify(&self.value, cx);
+        self.verify_state = VerifyState::Verifying;
+        self._verify_task = cx.spawn(|view, mut cx| async move {
+            let result = task.await;
+            _ = view.update(&mut cx, |view, cx| {
+                view.verify_state = match &result {
+                    Ok(()) => VerifyState::Success,
+                    Err(error) => VerifyState::Error(error.clone()),
+                };
+                cx.emit(InputEvent::Validate(result));
+                cx.notify();
+            });
+        });
+    }
+
     fn on_focus(&mut self, cx: &mut ViewContext<Self>) {
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.start(cx);
@@ -175,6 +355,11 @@ impl Render for OtpInput {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let blink_show = self.blink_cursor.read(cx).visible();
         let is_focused = self.focus_handle.is_focused(cx);
+        let border_color = match self.verify_state {
+            VerifyState::Success => Some(crate::green_500()),
+            VerifyState::Error(_) => Some(cx.theme().destructive),
+            _ => None,
+        };
 
         let text_size = match self.size {
             Size::XSmall => px(14.),
@@ -197,7 +382,7 @@ impl Render for OtpInput {
                 group_ix += 1;
             }
 
-            let is_input_focused = i == self.value.chars().count() && is_focused;
+            let is_input_focused = i == self.cursor && is_focused;
 
             groups[group_ix].push(
                 h_flex()
@@ -206,6 +391,7 @@ impl Render for OtpInput {
                     .border_color(cx.theme().input)
                     .bg(cx.theme().background)
                     .when(is_input_focused, |this| this.border_color(cx.theme().ring))
+                    .when_some(border_color, |this, color| this.border_color(color))
                     .shadow_sm()
                     .items_center()
                     .justify_center()
@@ -218,7 +404,10 @@ impl Render for OtpInput {
                         Size::Large => this.w_11().h_11(),
                         Size::Size(px) => this.w(px).h(px),
                     })
-                    .on_mouse_down(MouseButton::Left, cx.listener(Self::on_input_mouse_down))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, cx| this.on_input_mouse_down(i, cx)),
+                    )
                     .map(|this| match c {
                         Some(c) => {
                             if self.masked {
@@ -246,15 +435,38 @@ impl Render for OtpInput {
         }
 
         v_flex()
+            .key_context(CONTEXT)
             .track_focus(&self.focus_handle)
             .on_key_down(cx.listener(Self::on_key_down))
+            .on_action(cx.listener(Self::paste))
             .items_center()
+            .gap_1()
             .child(
-                h_flex().items_center().gap_5().children(
-                    groups
-                        .into_iter()
-                        .map(|inputs| h_flex().items_center().gap_1().children(inputs)),
-                ),
+                h_flex()
+                    .items_center()
+                    .gap_5()
+                    .children(
+                        groups
+                            .into_iter()
+                            .map(|inputs| h_flex().items_center().gap_1().children(inputs)),
+                    )
+                    .map(|this| match self.verify_state {
+                        VerifyState::Verifying => this.child(Indicator::new().small()),
+                        VerifyState::Success => this.child(
+                            Icon::new(IconName::Check)
+                                .text_color(crate::green_500())
+                                .small(),
+                        ),
+                        _ => this,
+                    }),
             )
+            .when_some(self.verify_error().cloned(), |this, error| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().destructive)
+                        .child(error),
+                )
+            })
     }
 }
@@ -0,0 +1,295 @@
+use gpui::{
+    deferred, div, prelude::FluentBuilder as _, px, AppContext, EventEmitter, FocusHandle,
+    FocusableView, InteractiveElement as _, IntoElement, MouseButton, ParentElement as _, Render,
+    SharedString, Styled as _, Subscription, View, ViewContext, VisualContext as _,
+};
+
+use crate::{
+    button::Button, h_flex, list::ListItem, theme::ActiveTheme, v_flex, Clickable as _, IconName,
+    Sizable as _, StyledExt as _,
+};
+
+use super::{Backspace, InputEvent, TextInput};
+
+/// The max number of the suggestions to show in the popup.
+const MAX_SUGGESTIONS: usize = 8;
+
+#[derive(Clone)]
+pub enum TagInputEvent {
+    /// The tags have changed, by adding or removing a tag.
+    Change(Vec<SharedString>),
+}
+
+/// A multi-value input, the typed text will be turned into a tag when the user presses enter or types a comma.
+pub struct TagInput {
+    input: View<TextInput>,
+    tags: Vec<SharedString>,
+    suggestions: Vec<SharedString>,
+    max: Option<usize>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TagInput {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let input = cx.new_view(|cx| TextInput::new(cx).appearance(false));
+        let _subscriptions = vec![cx.subscribe(&input, Self::on_input_event)];
+
+        Self {
+            input,
+            tags: vec![],
+            suggestions: vec![],
+            max: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set the suggestions to show in a popup when the typed text matches them.
+    pub fn suggestions(mut self, suggestions: impl IntoIterator<Item = SharedString>) -> Self {
+        self.suggestions = suggestions.into_iter().collect();
+        self
+    }
+
+    /// Set the max number of the tags, the typed text will be ignored when the tags are full.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Returns the input of the tag input, to set the placeholder or the validation.
+    pub fn input(&self) -> &View<TextInput> {
+        &self.input
+    }
+
+    /// Return the tags of the tag input.
+    pub fn tags(&self) -> &[SharedString] {
+        &self.tags
+    }
+
+    /// Set the tags of the tag input, the duplicated tags will be ignored.
+    pub fn set_tags(
+        &mut self,
+        tags: impl IntoIterator<Item = SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.tags.clear();
+        for tag in tags {
+            self.push_tag(tag);
+        }
+        cx.emit(TagInputEvent::Change(self.tags.clone()));
+        cx.notify();
+    }
+
+    pub fn focus(&self, cx: &mut ViewContext<Self>) {
+        self.input.focus_handle(cx).focus(cx);
+    }
+
+    fn is_full(&self) -> bool {
+        self.max.map_or(false, |max| self.tags.len() >= max)
+    }
+
+    fn contains(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Push the tag if it's not empty, not duplicated and the tags are not full, returns true if pushed.
+    fn push_tag(&mut self, tag: SharedString) -> bool {
+        let tag: SharedString = tag.trim().to_string().into();
+        if tag.is_empty() || self.is_full() || self.contains(&tag) {
+            return false;
+        }
+
+        self.tags.push(tag);
+        true
+    }
+
+    /// Add the tag and clear the input, the input is kept to edit if the tag is rejected.
+    fn add_tag(&mut self, tag: SharedString, cx: &mut ViewContext<Self>) {
+        if self.push_tag(tag) {
+            cx.emit(TagInputEvent::Change(self.tags.clone()));
+            self.input.update(cx, |input, cx| input.set_text("", cx));
+        }
+        cx.notify();
+    }
+
+    fn remove_tag(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix >= self.tags.len() {
+            return;
+        }
+
+        self.tags.remove(ix);
+        cx.emit(TagInputEvent::Change(self.tags.clone()));
+        cx.notify();
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: View<TextInput>,
+        event: &InputEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                let Some((tags, rest)) = text.rsplit_once(',') else {
+                    cx.notify();
+                    return;
+                };
+
+                // Keep the rejected tags in the input like `add_tag`, e.g.: a duplicated tag, or the tags are full.
+                // The commas are removed, otherwise the input will be split again.
+                let mut changed = false;
+                let mut kept = vec![];
+                for tag in tags.split(',') {
+                    if self.push_tag(tag.to_string().into()) {
+                        changed = true;
+                    } else if !tag.trim().is_empty() {
+                        kept.push(tag.trim());
+                    }
+                }
+                if changed {
+                    cx.emit(TagInputEvent::Change(self.tags.clone()));
+                }
+
+                let rest = rest.trim_start();
+                if !rest.is_empty() || kept.is_empty() {
+                    kept.push(rest);
+                }
+                let text = kept.join(" ");
+                self.input.update(cx, |input, cx| input.set_text(text, cx));
+                cx.notify();
+            }
+            InputEvent::PressEnter => {
+                let text = self.input.read(cx).text();
+                self.add_tag(text, cx);
+            }
+            _ => {}
+        }
+    }
+
+    /// Delete the last tag when pressing backspace on the empty input.
+    fn on_backspace(&mut self, _: &Backspace, cx: &mut ViewContext<Self>) {
+        if !self.input.read(cx).text().is_empty() || self.tags.is_empty() {
+            return;
+        }
+
+        cx.stop_propagation();
+        self.remove_tag(self.tags.len() - 1, cx);
+    }
+
+    fn matched_suggestions(&self, cx: &AppContext) -> Vec<SharedString> {
+        let query = self.input.read(cx).text().trim().to_lowercase();
+        if query.is_empty() || self.is_full() {
+            return vec![];
+        }
+
+        self.suggestions
+            .iter()
+            .filter(|s| s.to_lowercase().contains(&query) && !self.contains(s))
+            .take(MAX_SUGGESTIONS)
+            .cloned()
+            .collect()
+    }
+
+    fn render_tag(
+        &self,
+        ix: usize,
+        tag: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .gap_0p5()
+            .pl_1p5()
+            .rounded(px(cx.theme().radius))
+            .bg(cx.theme().secondary)
+            .text_sm()
+            .child(tag)
+            .child(
+                Button::new(("remove-tag", ix), cx)
+                    .icon(IconName::Close)
+                    .ghost()
+                    .compact()
+                    .xsmall()
+                    .on_click(cx.listener(move |this, _, cx| this.remove_tag(ix, cx))),
+            )
+    }
+}
+
+impl EventEmitter<TagInputEvent> for TagInput {}
+
+impl FocusableView for TagInput {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl Render for TagInput {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let focused = self.input.focus_handle(cx).is_focused(cx);
+        let suggestions = self.matched_suggestions(cx);
+
+        div()
+            .relative()
+            .w_full()
+            .capture_action(cx.listener(Self::on_backspace))
+            .child(
+                h_flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .pl_1()
+                    .py_1()
+                    .bg(cx.theme().background)
+                    .border_1()
+                    .border_color(cx.theme().input)
+                    .rounded(px(cx.theme().radius))
+                    .shadow_sm()
+                    .when(focused, |this| this.outline(cx))
+                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _, cx| this.focus(cx)))
+                    .children(
+                        self.tags
+                            .clone()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, tag)| self.render_tag(ix, tag, cx)),
+                    )
+                    .child(div().flex_1().min_w(px(80.)).child(self.input.clone())),
+            )
+            .when(focused && !suggestions.is_empty(), |this| {
+                this.child(
+                    deferred(
+                        v_flex()
+                            .occlude()
+                            .absolute()
+                            .top_full()
+                            .left_0()
+                            .w_full()
+                            .mt_1p5()
+                            .py_1()
+                            .bg(cx.theme().background)
+                            .border_1()
+                            .border_color(cx.theme().input)
+                            .rounded(px(cx.theme().radius))
+                            .shadow_md()
+                            .children(suggestions.into_iter().enumerate().map(
+                                |(ix, suggestion)| {
+                                    // Add the tag on mouse down, before the input is blurred.
+                                    div()
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener({
+                                                let suggestion = suggestion.clone();
+                                                move |this, _, cx| {
+                                                    cx.stop_propagation();
+                                                    this.add_tag(suggestion.clone(), cx);
+                                                }
+                                            }),
+                                        )
+                                        .child(
+                                            ListItem::new(("tag-suggestion", ix)).child(suggestion),
+                                        )
+                                },
+                            )),
+                    )
+                    .with_priority(1),
+                )
+            })
+    }
+}
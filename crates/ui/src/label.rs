@@ -3,7 +3,7 @@ use gpui::{
     Styled, WindowContext,
 };
 
-use crate::{h_flex, theme::ActiveTheme};
+use crate::{fuzzy, h_flex, theme::ActiveTheme, StyledExt as _};
 
 #[derive(Default, PartialEq, Eq)]
 pub enum TextAlign {
@@ -103,3 +103,54 @@ impl RenderOnce for Label {
         )
     }
 }
+
+/// A single line label with the highlighted chars, e.g.: the matched chars of the [`fuzzy`] search.
+///
+/// The text color is inherited from the parent, only the highlighted chars are colored.
+#[derive(IntoElement)]
+pub struct HighlightedLabel {
+    base: Div,
+    label: SharedString,
+    positions: Vec<usize>,
+}
+
+impl HighlightedLabel {
+    /// Create a label with the byte offsets of the chars to highlight, e.g.: the `StringMatch::positions`.
+    pub fn new(label: impl Into<SharedString>, positions: impl Into<Vec<usize>>) -> Self {
+        Self {
+            base: h_flex().whitespace_nowrap(),
+            label: label.into(),
+            positions: positions.into(),
+        }
+    }
+}
+
+impl Styled for HighlightedLabel {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for HighlightedLabel {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let mut children = vec![];
+        let mut offset = 0;
+        for range in fuzzy::highlight_ranges(&self.label, &self.positions) {
+            if offset < range.start {
+                children.push(div().child(self.label[offset..range.start].to_string()));
+            }
+            children.push(
+                div()
+                    .text_color(cx.theme().primary)
+                    .font_semibold()
+                    .child(self.label[range.clone()].to_string()),
+            );
+            offset = range.end;
+        }
+        if offset < self.label.len() {
+            children.push(div().child(self.label[offset..].to_string()));
+        }
+
+        self.base.children(children)
+    }
+}
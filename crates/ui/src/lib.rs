@@ -17,6 +17,7 @@ pub mod clipboard;
 pub mod context_menu;
 pub mod divider;
 pub mod dropdown;
+pub mod fuzzy;
 pub mod indicator;
 pub mod input;
 pub mod label;
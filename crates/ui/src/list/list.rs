@@ -1,13 +1,14 @@
 use std::time::Duration;
 use std::{cell::Cell, rc::Rc};
 
+use crate::indicator::Indicator;
 use crate::input::{InputEvent, TextInput};
 use crate::scroll::ScrollbarState;
 use crate::theme::ActiveTheme;
-use crate::IconName;
+use crate::{h_flex, IconName, Sizable as _};
 use crate::{scroll::Scrollbar, v_flex};
 use gpui::{
-    actions, div, prelude::FluentBuilder, uniform_list, AppContext, FocusHandle, FocusableView,
+    actions, div, prelude::FluentBuilder, px, uniform_list, AppContext, FocusHandle, FocusableView,
     InteractiveElement, IntoElement, KeyBinding, Length, ListSizingBehavior, MouseButton,
     ParentElement, Render, Styled, Task, UniformListScrollHandle, View, ViewContext, VisualContext,
 };
@@ -26,6 +27,15 @@ pub fn init(cx: &mut AppContext) {
     ]);
 }
 
+/// The kind of a row in the [`List`], only the `Item` rows can be selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListRowKind {
+    Item,
+    /// A section header, it stays sticky at the top while scrolling the section.
+    Header,
+    Separator,
+}
+
 /// A delegate for the List.
 #[allow(unused)]
 pub trait ListDelegate: Sized + 'static {
@@ -45,6 +55,37 @@ pub trait ListDelegate: Sized + 'static {
     /// Return None will skip the item.
     fn render_item(&self, ix: usize, cx: &mut ViewContext<List<Self>>) -> Option<Self::Item>;
 
+    /// Return the kind of the row at the given index, default is `ListRowKind::Item`.
+    ///
+    /// The headers and separators are also rendered by `render_item`, and they must have the same height as the items.
+    fn row_kind(&self, ix: usize) -> ListRowKind {
+        ListRowKind::Item
+    }
+
+    /// Return true if the item at the given index is disabled, it will be skipped by the selection and can't be confirmed.
+    fn is_item_disabled(&self, ix: usize) -> bool {
+        false
+    }
+
+    /// Return true if there are more items to load, e.g.: the next page from the server.
+    ///
+    /// The `load_more` will be called when the list is scrolled near the end.
+    fn has_more(&self) -> bool {
+        false
+    }
+
+    /// Return the number of the remaining items below the visible ones to start loading more, default is 10.
+    fn load_more_threshold(&self) -> usize {
+        10
+    }
+
+    /// Load the next page of the items and append them to the end.
+    ///
+    /// A loading indicator is shown at the bottom of the list until the task is finished.
+    fn load_more(&mut self, cx: &mut ViewContext<List<Self>>) -> Task<()> {
+        Task::Ready(Some(()))
+    }
+
     /// Return a Element to show when list is empty.
     fn render_empty(&self, cx: &mut ViewContext<List<Self>>) -> impl IntoElement {
         div()
@@ -72,6 +113,8 @@ pub struct List<D: ListDelegate> {
     query_input: Option<View<TextInput>>,
     last_query: Option<String>,
     loading: bool,
+    /// True if the `ListDelegate::load_more` is in progress.
+    loading_more: bool,
 
     enable_scrollbar: bool,
     vertical_scroll_handle: UniformListScrollHandle,
@@ -79,6 +122,7 @@ pub struct List<D: ListDelegate> {
 
     selected_index: Option<usize>,
     _search_task: Task<()>,
+    _load_more_task: Task<()>,
 }
 
 impl<D> List<D>
@@ -108,7 +152,9 @@ where
             max_height: None,
             enable_scrollbar: true,
             loading: false,
+            loading_more: false,
             _search_task: Task::Ready(None),
+            _load_more_task: Task::Ready(None),
         }
     }
 
@@ -174,12 +220,83 @@ where
         ))
     }
 
-    fn scroll_to_selected_item(&mut self, _cx: &mut ViewContext<Self>) {
+    /// Scroll the list to make the selected item visible.
+    pub fn scroll_to_selected_item(&mut self, _cx: &mut ViewContext<Self>) {
         if let Some(ix) = self.selected_index {
             self.vertical_scroll_handle.scroll_to_item(ix);
         }
     }
 
+    fn is_selectable(&self, ix: usize) -> bool {
+        self.delegate.row_kind(ix) == ListRowKind::Item && !self.delegate.is_item_disabled(ix)
+    }
+
+    /// Select the next or previous selectable item, and wrap around at the end.
+    fn select_by_step(&mut self, forward: bool, cx: &mut ViewContext<Self>) {
+        let count = self.delegate.items_count();
+        let mut ix = self.selected_index;
+        for _ in 0..count {
+            let next_ix = match (ix, forward) {
+                // Don't wrap around to the start while there are more items to load.
+                (Some(ix), true) if ix + 1 == count && self.delegate.has_more() => return,
+                (Some(ix), true) => (ix + 1) % count,
+                (Some(ix), false) => (ix + count - 1) % count,
+                (None, true) => 0,
+                (None, false) => count - 1,
+            };
+
+            if self.is_selectable(next_ix) {
+                self.selected_index = Some(next_ix);
+                self.scroll_to_selected_item(cx);
+                cx.notify();
+                return;
+            }
+            ix = Some(next_ix);
+        }
+    }
+
+    /// Return true if the delegate has more items, and the `visible_end` is near the end of the items.
+    fn should_load_more(&self, visible_end: usize) -> bool {
+        !self.loading
+            && !self.loading_more
+            && self.delegate.has_more()
+            && visible_end + self.delegate.load_more_threshold() >= self.delegate.items_count()
+    }
+
+    /// Load the next page of the items, the selected index is kept as the items are appended.
+    fn load_more(&mut self, visible_end: usize, cx: &mut ViewContext<Self>) {
+        if !self.should_load_more(visible_end) {
+            return;
+        }
+
+        self.loading_more = true;
+        let load_more = self.delegate.load_more(cx);
+        self._load_more_task = cx.spawn(|this, mut cx| async move {
+            load_more.await;
+            _ = this.update(&mut cx, |this, cx| {
+                this.loading_more = false;
+                cx.notify();
+            });
+        });
+        cx.notify();
+    }
+
+    /// Return the index of the header to stick at the top, if the header has been scrolled out.
+    fn sticky_header_index(&self) -> Option<usize> {
+        let state = self.vertical_scroll_handle.0.borrow();
+        let item_height = state.last_item_height?;
+        let scroll_top = -state.base_handle.offset().y;
+        if scroll_top <= px(0.) || item_height <= px(0.) {
+            return None;
+        }
+
+        let first_visible_ix = ((scroll_top / item_height) as usize)
+            .min(self.delegate.items_count().saturating_sub(1));
+        (0..=first_visible_ix)
+            .rev()
+            .find(|ix| self.delegate.row_kind(*ix) == ListRowKind::Header)
+    }
+
     fn on_query_input_event(
         &mut self,
         _: View<TextInput>,
@@ -193,6 +310,10 @@ where
                     return;
                 }
 
+                // The loading page is outdated by the new query.
+                self.loading_more = false;
+                self._load_more_task = Task::Ready(None);
+
                 self.set_loading(true, cx);
                 let search = self.delegate.perform_search(&text, cx);
 
@@ -234,44 +355,22 @@ where
         if self.delegate.items_count() == 0 {
             return;
         }
+        if let Some(ix) = self.selected_index {
+            if !self.is_selectable(ix) {
+                return;
+            }
+        }
 
         self.delegate.confirm(self.selected_index, cx);
         cx.notify();
     }
 
     fn action_select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
-        if self.delegate.items_count() == 0 {
-            return;
-        }
-
-        let selected_index = self.selected_index.unwrap_or(0);
-        if selected_index > 0 {
-            self.selected_index = Some(selected_index - 1);
-        } else {
-            self.selected_index = Some(self.delegate.items_count() - 1);
-        }
-
-        self.scroll_to_selected_item(cx);
-        cx.notify();
+        self.select_by_step(false, cx);
     }
 
     fn action_select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
-        if self.delegate.items_count() == 0 {
-            return;
-        }
-
-        if let Some(selected_index) = self.selected_index {
-            if selected_index < self.delegate.items_count() - 1 {
-                self.selected_index = Some(selected_index + 1);
-            } else {
-                self.selected_index = Some(0);
-            }
-        } else {
-            self.selected_index = Some(0);
-        }
-
-        self.scroll_to_selected_item(cx);
-        cx.notify();
+        self.select_by_step(true, cx);
     }
 }
 
@@ -303,6 +402,13 @@ where
         };
 
         let selected_bg = cx.theme().list_active;
+        // The `uniform_list` is not rendered without items, so load the first page here.
+        if items_count == 0 && self.should_load_more(0) {
+            cx.defer(|this, cx| this.load_more(0, cx));
+        }
+        let sticky_header = self
+            .sticky_header_index()
+            .and_then(|ix| self.delegate.render_item(ix, cx));
 
         v_flex()
             .key_context("List")
@@ -331,15 +437,22 @@ where
                     .relative()
                     .when_some(self.max_height, |this, h| this.max_h(h))
                     .overflow_hidden()
-                    .when(items_count == 0, |this| {
+                    .when(items_count == 0 && !self.loading_more, |this| {
                         this.child(self.delegate().render_empty(cx))
                     })
                     .when(items_count > 0, |this| {
                         this.child(
                             uniform_list(view, "uniform-list", items_count, {
                                 move |list, visible_range, cx| {
+                                    let visible_end = visible_range.end;
+                                    if list.should_load_more(visible_end) {
+                                        cx.defer(move |list, cx| list.load_more(visible_end, cx));
+                                    }
+
                                     visible_range
                                         .map(|ix| {
+                                            let selectable = list.is_selectable(ix);
+
                                             div()
                                                 .id("list-item")
                                                 .w_full()
@@ -352,14 +465,16 @@ where
                                                         })
                                                     },
                                                 )
-                                                .on_mouse_down(
-                                                    MouseButton::Left,
-                                                    cx.listener(move |this, _, cx| {
-                                                        cx.stop_propagation();
-                                                        this.selected_index = Some(ix);
-                                                        this.action_confirm(&Confirm, cx);
-                                                    }),
-                                                )
+                                                .when(selectable, |this| {
+                                                    this.on_mouse_down(
+                                                        MouseButton::Left,
+                                                        cx.listener(move |this, _, cx| {
+                                                            cx.stop_propagation();
+                                                            this.selected_index = Some(ix);
+                                                            this.action_confirm(&Confirm, cx);
+                                                        }),
+                                                    )
+                                                })
                                         })
                                         .collect::<Vec<_>>()
                                 }
@@ -370,6 +485,26 @@ where
                             .into_any_element(),
                         )
                     })
+                    .when(self.loading_more, |this| {
+                        this.child(
+                            h_flex()
+                                .justify_center()
+                                .py_1()
+                                .child(Indicator::new().small().color(cx.theme().muted_foreground)),
+                        )
+                    })
+                    .when_some(sticky_header, |this, header| {
+                        this.child(
+                            div()
+                                .occlude()
+                                .absolute()
+                                .top_0()
+                                .left_0()
+                                .right_0()
+                                .bg(cx.theme().background)
+                                .child(header),
+                        )
+                    })
                     .children(self.render_scrollbar(cx)),
             )
     }
@@ -115,7 +115,11 @@ impl RenderOnce for ListItem {
         let is_active = self.selected || self.confirmed;
 
         self.base
-            .text_color(cx.theme().foreground)
+            .text_color(if self.disabled {
+                cx.theme().muted_foreground
+            } else {
+                cx.theme().foreground
+            })
             .relative()
             .items_center()
             .justify_between()
@@ -42,12 +42,19 @@ enum PopupMenuItem {
         icon: Option<Icon>,
         label: SharedString,
         handler: Rc<dyn Fn(&mut WindowContext)>,
+        disabled: bool,
     },
 }
 
 impl PopupMenuItem {
     fn is_clickable(&self) -> bool {
-        !matches!(self, PopupMenuItem::Separator)
+        matches!(
+            self,
+            PopupMenuItem::Item {
+                disabled: false,
+                ..
+            }
+        )
     }
 
     fn has_icon(&self) -> bool {
@@ -104,7 +111,18 @@ impl PopupMenu {
 
     /// Add Menu Item
     pub fn menu(mut self, label: impl Into<SharedString>, action: Box<dyn Action>) -> Self {
-        self.add_menu_item(label, None, action);
+        self.add_menu_item(label, None, action, false);
+        self
+    }
+
+    /// Add Menu Item with disabled state, the disabled item is shown muted and can't be clicked.
+    pub fn menu_with_disabled(
+        mut self,
+        label: impl Into<SharedString>,
+        action: Box<dyn Action>,
+        disabled: bool,
+    ) -> Self {
+        self.add_menu_item(label, None, action, disabled);
         self
     }
 
@@ -115,6 +133,7 @@ impl PopupMenu {
             icon: None,
             label: label.into(),
             handler: Rc::new(move |cx| cx.open_url(&href)),
+            disabled: false,
         });
         self
     }
@@ -131,6 +150,7 @@ impl PopupMenu {
             icon: Some(icon.into()),
             label: label.into(),
             handler: Rc::new(move |cx| cx.open_url(&href)),
+            disabled: false,
         });
         self
     }
@@ -141,7 +161,7 @@ impl PopupMenu {
         icon: impl Into<Icon>,
         action: Box<dyn Action>,
     ) -> Self {
-        self.add_menu_item(label, Some(icon.into()), action);
+        self.add_menu_item(label, Some(icon.into()), action, false);
         self
     }
 
@@ -153,9 +173,9 @@ impl PopupMenu {
         action: Box<dyn Action>,
     ) -> Self {
         if checked {
-            self.add_menu_item(label, Some(IconName::Check.into()), action);
+            self.add_menu_item(label, Some(IconName::Check.into()), action, false);
         } else {
-            self.add_menu_item(label, None, action);
+            self.add_menu_item(label, None, action, false);
         }
 
         self
@@ -166,6 +186,7 @@ impl PopupMenu {
         label: impl Into<SharedString>,
         icon: Option<Icon>,
         action: Box<dyn Action>,
+        disabled: bool,
     ) -> &mut Self {
         if icon.is_some() {
             self.has_icon = true;
@@ -178,6 +199,7 @@ impl PopupMenu {
                 cx.activate_window();
                 cx.dispatch_action(action.boxed_clone());
             }),
+            disabled,
         });
         self
     }
@@ -207,7 +229,11 @@ impl PopupMenu {
             Some(index) => {
                 let item = self.menu_items.get(index);
                 match item {
-                    Some(PopupMenuItem::Item { handler, .. }) => {
+                    Some(PopupMenuItem::Item {
+                        handler,
+                        disabled: false,
+                        ..
+                    }) => {
                         handler(cx);
                         self.dismiss(&Dismiss, cx)
                     }
@@ -292,8 +318,18 @@ impl Render for PopupMenu {
                             .border_0()
                             .bg(cx.theme().border),
                     ),
-                    PopupMenuItem::Item { icon, label, .. } => {
-                        this.py(px(2.)).px_2().rounded_md().text_sm().child(
+                    PopupMenuItem::Item {
+                        icon,
+                        label,
+                        disabled,
+                        ..
+                    } => this
+                        .disabled(*disabled)
+                        .py(px(2.))
+                        .px_2()
+                        .rounded_md()
+                        .text_sm()
+                        .child(
                             h_flex()
                                 .size_full()
                                 .items_center()
@@ -311,8 +347,7 @@ impl Render for PopupMenu {
                                         .when(has_icon, |this| this.pl(px(19.)).pr_2())
                                         .child(label.clone()),
                                 ),
-                        )
-                    }
+                        ),
                 }
             }))
     }
@@ -1,17 +1,30 @@
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::Cell,
+    collections::{BTreeSet, HashMap},
+    io::{self, Write},
+    ops::Range,
+    rc::Rc,
+};
 
 use crate::{
+    button::Button,
+    checkbox::Checkbox,
+    dropdown::{Dropdown, DropdownEvent, SearchableVec},
     h_flex,
+    indicator::Indicator,
+    input::{InputEvent, TextInput},
+    popover::Popover,
     scroll::{ScrollableAxis, ScrollableMask, Scrollbar, ScrollbarState},
     theme::ActiveTheme,
-    v_flex, Icon, IconName,
+    v_flex, Icon, IconName, Selection, Sizable as _,
 };
 use gpui::{
-    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, AppContext, Bounds, Div,
-    DragMoveEvent, EntityId, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
-    IntoElement, KeyBinding, MouseButton, ParentElement, Pixels, Point, Render, ScrollHandle,
-    SharedString, StatefulInteractiveElement as _, Styled, UniformListScrollHandle, ViewContext,
-    VisualContext as _, WindowContext,
+    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, AnchorCorner, AnyElement,
+    AppContext, Bounds, ClipboardItem, Div, DragMoveEvent, EntityId, EventEmitter, FocusHandle,
+    FocusableView, Hsla, InteractiveElement, IntoElement, KeyBinding, KeyContext, MouseButton,
+    MouseDownEvent, ParentElement, Pixels, Point, Render, ScrollHandle, SharedString,
+    StatefulInteractiveElement as _, Styled, Subscription, Task, UniformListScrollHandle, View,
+    ViewContext, VisualContext as _, WindowContext,
 };
 
 actions!(
@@ -20,27 +33,65 @@ actions!(
         Cancel,
         SelectPrev,
         SelectNext,
+        SelectPrevExtend,
+        SelectNextExtend,
+        SelectAll,
         SelectPrevColumn,
-        SelectNextColumn
+        SelectNextColumn,
+        EditCell,
+        EditNextCell,
+        EditPrevCell,
+        Copy,
+        CopyAsCsv
     ]
 );
 
+mod export;
+mod filter;
+
+pub use export::TableFormat;
+use filter::FilterPanel;
+pub use filter::{ColFilter, FilterValue};
+
+const CONTEXT: &str = "Table";
+/// The extra key context identifier when a cell is editing.
+const EDITING: &str = "editing";
+const EDITING_CONTEXT: &str = "Table && editing";
+
 pub fn init(cx: &mut AppContext) {
-    let context = Some("Table");
+    let context = Some(CONTEXT);
     cx.bind_keys([
         KeyBinding::new("escape", Cancel, context),
+        KeyBinding::new("enter", EditCell, context),
+        KeyBinding::new("tab", EditNextCell, Some(EDITING_CONTEXT)),
+        KeyBinding::new("shift-tab", EditPrevCell, Some(EDITING_CONTEXT)),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("shift-up", SelectPrevExtend, context),
+        KeyBinding::new("shift-down", SelectNextExtend, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", SelectAll, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", SelectAll, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", Copy, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", Copy, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-shift-c", CopyAsCsv, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-c", CopyAsCsv, context),
         KeyBinding::new("left", SelectPrevColumn, context),
         KeyBinding::new("right", SelectNextColumn, context),
     ]);
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct ColGroup {
     width: Option<Pixels>,
     bounds: Bounds<Pixels>,
     sort: Option<ColSort>,
+    filter: Option<FilterValue>,
 }
 
 #[derive(Clone)]
@@ -86,9 +137,53 @@ enum SelectionState {
     Row,
 }
 
+/// The value of a cell for editing, the variant decides the editor of the cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// Edit by a `TextInput`.
+    Text(SharedString),
+    /// Edit by a `Checkbox`.
+    Bool(bool),
+    /// Edit by a `Dropdown` with the options, and the selected option index.
+    Select(Vec<SharedString>, Option<usize>),
+}
+
+enum CellEditor {
+    Text(View<TextInput>),
+    Bool(bool),
+    Select(
+        Vec<SharedString>,
+        View<Dropdown<SearchableVec<SharedString>>>,
+    ),
+}
+
+struct EditingCell {
+    row_ix: usize,
+    col_ix: usize,
+    editor: CellEditor,
+    /// The validation error returned by `TableDelegate::commit_cell`.
+    error: Option<SharedString>,
+    _subscription: Option<Subscription>,
+}
+
+impl EditingCell {
+    fn value(&self, cx: &WindowContext) -> CellValue {
+        match &self.editor {
+            CellEditor::Text(input) => CellValue::Text(input.read(cx).text()),
+            CellEditor::Bool(checked) => CellValue::Bool(*checked),
+            CellEditor::Select(options, dropdown) => {
+                CellValue::Select(options.clone(), dropdown.read(cx).selected_index(cx))
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum TableEvent {
+    /// The active row is changed.
     SelectRow(usize),
+    /// The selected rows are changed, with all the selected row indexes in ascending order.
+    SelectRows(Vec<usize>),
     SelectCol(usize),
     ColWidthsChanged(Vec<Option<Pixels>>),
 }
@@ -105,12 +200,26 @@ pub struct Table<D: TableDelegate> {
     scrollbar_state: Rc<Cell<ScrollbarState>>,
 
     selection_state: SelectionState,
+    /// The active row, the keyboard selection starts from it.
     selected_row: Option<usize>,
+    /// All the selected rows, including the `selected_row`.
+    selected_rows: BTreeSet<usize>,
+    /// The start row of the range selection by shift-click or shift-up/down.
+    selection_anchor: Option<usize>,
     selected_col: Option<usize>,
 
     /// The column index that is being resized.
     resizing_col: Option<usize>,
 
+    /// The cell that is being edited.
+    editing: Option<EditingCell>,
+
+    /// The rows that are loading children, mapping from the load id to the row index.
+    loading_rows: HashMap<usize, usize>,
+    next_load_id: usize,
+    /// The rows count after the last expand or collapse, to know how many children rows are inserted or removed.
+    rows_count: usize,
+
     /// Set stripe style of the table.
     stripe: bool,
 }
@@ -146,6 +255,21 @@ pub trait TableDelegate: Sized + 'static {
     /// Perform sort on the column at the given index.
     fn perform_sort(&mut self, col_ix: usize, sort: ColSort, cx: &mut ViewContext<Table<Self>>) {}
 
+    /// Returns the filter of the column at the given index, return `None` if the column can't be filtered. Default: None
+    fn col_filter(&self, col_ix: usize) -> Option<ColFilter> {
+        None
+    }
+
+    /// Perform filter with all the active filters, the `filters` are pairs of the column index and the filter value.
+    ///
+    /// The delegate should re-query the rows, and the selection of the table will be cleared.
+    fn perform_filter(
+        &mut self,
+        filters: &[(usize, FilterValue)],
+        cx: &mut ViewContext<Table<Self>>,
+    ) {
+    }
+
     /// Render the header cell at the given column index, default to the column name.
     fn render_th(&self, col_ix: usize, cx: &mut ViewContext<Table<Self>>) -> impl IntoElement {
         div().size_full().child(self.col_name(col_ix))
@@ -164,6 +288,90 @@ pub trait TableDelegate: Sized + 'static {
         cx: &mut ViewContext<Table<Self>>,
     ) -> impl IntoElement;
 
+    /// Returns the number of the columns fixed on the left side, they will not scroll horizontally. Default: 0
+    fn fixed_left_cols_count(&self) -> usize {
+        0
+    }
+
+    /// Returns the number of the columns fixed on the right side, they will not scroll horizontally. Default: 0
+    fn fixed_right_cols_count(&self) -> usize {
+        0
+    }
+
+    /// Return true to show a footer row pinned at the bottom of the table, e.g.: the summary of the rows. Default: false
+    fn has_foot(&self) -> bool {
+        false
+    }
+
+    /// Render the footer cell at the given column index.
+    fn render_tf(&self, col_ix: usize, cx: &mut ViewContext<Table<Self>>) -> impl IntoElement {
+        div()
+    }
+
+    /// Return true to render the rows as a tree. Default: false
+    ///
+    /// The delegate keeps the visible rows in a flat list, the first column will be indented by the `row_depth`
+    /// and show the expand/collapse toggle for the rows that have children.
+    fn is_tree(&self) -> bool {
+        false
+    }
+
+    /// Returns the depth of the row in the tree, the top level rows are 0. Default: 0
+    fn row_depth(&self, row_ix: usize) -> usize {
+        0
+    }
+
+    /// Returns true if the row has children, even if they are not loaded yet. Default: false
+    fn row_has_children(&self, row_ix: usize) -> bool {
+        false
+    }
+
+    /// Returns true if the children of the row are visible. Default: false
+    fn row_expanded(&self, row_ix: usize) -> bool {
+        false
+    }
+
+    /// Expand or collapse the row at the given index.
+    ///
+    /// The delegate should insert the children rows after the row when expanding, and remove them when collapsing.
+    /// Return a `Task` to load the children lazily, the toggle will show a loading indicator until the task is finished.
+    fn perform_expand(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        cx: &mut ViewContext<Table<Self>>,
+    ) -> Option<Task<()>> {
+        None
+    }
+
+    /// Returns the plain text of the cell, used to copy and export the table. Default: empty
+    fn cell_text(&self, row_ix: usize, col_ix: usize) -> SharedString {
+        SharedString::default()
+    }
+
+    /// Return false to prevent the row at the given index from being selected. Default: true
+    fn can_select_row(&self, row_ix: usize) -> bool {
+        true
+    }
+
+    /// Returns the value of the cell to edit, return `None` if the cell is not editable. Default: None
+    fn cell_value(&self, row_ix: usize, col_ix: usize) -> Option<CellValue> {
+        None
+    }
+
+    /// Commit the edited value of the cell at the given row and column.
+    ///
+    /// Return `Err` with a message to reject the value, the message will be shown in the cell.
+    fn commit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        value: CellValue,
+        cx: &mut ViewContext<Table<Self>>,
+    ) -> Result<(), SharedString> {
+        Ok(())
+    }
+
     /// Return true to enable loop selection on the table.
     ///
     /// When the prev/next selection is out of the table bounds, the selection will loop to the other side.
@@ -196,8 +404,14 @@ where
             scrollbar_state: Rc::new(Cell::new(ScrollbarState::new())),
             selection_state: SelectionState::Row,
             selected_row: None,
+            selected_rows: BTreeSet::new(),
+            selection_anchor: None,
             selected_col: None,
             resizing_col: None,
+            editing: None,
+            loading_rows: HashMap::new(),
+            next_load_id: 0,
+            rows_count: 0,
             bounds: Bounds::default(),
             stripe: true,
         };
@@ -225,74 +439,541 @@ where
                 width: self.delegate.col_width(col_ix),
                 bounds: Bounds::default(),
                 sort: self.delegate.col_sort(col_ix),
+                filter: None,
             })
             .collect();
         cx.notify();
     }
 
+    /// Returns the active row.
+    pub fn selected_row(&self) -> Option<usize> {
+        self.selected_row
+    }
+
+    /// Returns all the selected rows.
+    pub fn selected_rows(&self) -> &BTreeSet<usize> {
+        &self.selected_rows
+    }
+
+    /// Select all the rows that can be selected.
+    pub fn select_all(&mut self, cx: &mut ViewContext<Self>) {
+        self.selection_state = SelectionState::Row;
+        self.selected_rows = (0..self.delegate.rows_count())
+            .filter(|row_ix| self.delegate.can_select_row(*row_ix))
+            .collect();
+        if self.selected_row.is_none() {
+            self.selected_row = self.selected_rows.first().copied();
+        }
+        self.selection_anchor = self.selected_row;
+        self.emit_selected_rows(cx);
+    }
+
+    /// Select only the given row, and make it the active row.
     fn set_selected_row(&mut self, row_ix: usize, cx: &mut ViewContext<Self>) {
+        if !self.commit_editing(cx) || !self.delegate.can_select_row(row_ix) {
+            return;
+        }
+
         self.selection_state = SelectionState::Row;
         self.selected_row = Some(row_ix);
-        if let Some(row_ix) = self.selected_row {
-            self.vertical_scroll_handle.scroll_to_item(row_ix);
+        self.selected_rows = BTreeSet::from([row_ix]);
+        self.selection_anchor = Some(row_ix);
+        self.vertical_scroll_handle.scroll_to_item(row_ix);
+        cx.emit(TableEvent::SelectRow(row_ix));
+        self.emit_selected_rows(cx);
+    }
+
+    /// Add or remove the given row from the selection, e.g. by ctrl-click.
+    fn toggle_selected_row(&mut self, row_ix: usize, cx: &mut ViewContext<Self>) {
+        if !self.commit_editing(cx) || !self.delegate.can_select_row(row_ix) {
+            return;
         }
+
+        if self.selection_state != SelectionState::Row {
+            self.selection_state = SelectionState::Row;
+            self.selected_rows.clear();
+        }
+
+        if !self.selected_rows.remove(&row_ix) {
+            self.selected_rows.insert(row_ix);
+        }
+        self.selected_row = Some(row_ix);
+        self.selection_anchor = Some(row_ix);
         cx.emit(TableEvent::SelectRow(row_ix));
+        self.emit_selected_rows(cx);
+    }
+
+    /// Select the rows from the anchor row to the given row, e.g. by shift-click.
+    fn select_row_range(&mut self, row_ix: usize, cx: &mut ViewContext<Self>) {
+        if !self.commit_editing(cx) {
+            return;
+        }
+
+        let anchor = self.selection_anchor.unwrap_or(row_ix);
+        let range = if anchor <= row_ix {
+            anchor..=row_ix
+        } else {
+            row_ix..=anchor
+        };
+
+        self.selection_state = SelectionState::Row;
+        self.selected_rows = range
+            .filter(|row_ix| self.delegate.can_select_row(*row_ix))
+            .collect();
+        self.selected_row = Some(row_ix);
+        self.selection_anchor = Some(anchor);
+        self.vertical_scroll_handle.scroll_to_item(row_ix);
+        cx.emit(TableEvent::SelectRow(row_ix));
+        self.emit_selected_rows(cx);
+    }
+
+    /// Expand or collapse the row at the given index, if it has children.
+    pub fn set_row_expanded(&mut self, row_ix: usize, expanded: bool, cx: &mut ViewContext<Self>) {
+        if !self.delegate.is_tree()
+            || !self.delegate.row_has_children(row_ix)
+            || self.delegate.row_expanded(row_ix) == expanded
+            || self.is_loading_row(row_ix)
+        {
+            return;
+        }
+
+        self.cancel_editing(cx);
+        self.rows_count = self.delegate.rows_count();
+        let task = self.delegate.perform_expand(row_ix, expanded, cx);
+        self.children_changed(row_ix);
         cx.notify();
+
+        let Some(task) = task else {
+            return;
+        };
+
+        let load_id = self.next_load_id;
+        self.next_load_id += 1;
+        self.loading_rows.insert(load_id, row_ix);
+        cx.spawn(|view, mut cx| async move {
+            task.await;
+            _ = view.update(&mut cx, |this, cx| {
+                if let Some(row_ix) = this.loading_rows.remove(&load_id) {
+                    this.children_changed(row_ix);
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Toggle the expanded state of the row at the given index.
+    pub fn toggle_row_expanded(&mut self, row_ix: usize, cx: &mut ViewContext<Self>) {
+        let expanded = self.delegate.row_expanded(row_ix);
+        self.set_row_expanded(row_ix, !expanded, cx);
+    }
+
+    fn is_loading_row(&self, row_ix: usize) -> bool {
+        self.loading_rows.values().any(|ix| *ix == row_ix)
+    }
+
+    /// Shift the row indexes after the `row_ix`, when the children rows of it are inserted or removed.
+    fn children_changed(&mut self, row_ix: usize) {
+        let rows_count = self.delegate.rows_count();
+        let delta = rows_count as isize - self.rows_count as isize;
+        self.rows_count = rows_count;
+        if delta == 0 {
+            return;
+        }
+
+        // Returns None if the row is removed.
+        let shift = |ix: usize| {
+            if ix <= row_ix {
+                Some(ix)
+            } else if delta < 0 && ix <= row_ix + delta.unsigned_abs() {
+                None
+            } else {
+                Some(ix.saturating_add_signed(delta))
+            }
+        };
+
+        self.selected_rows = self
+            .selected_rows
+            .iter()
+            .filter_map(|ix| shift(*ix))
+            .collect();
+        if let Some(ix) = self.selected_row {
+            // The active row is hidden, move the active row to the parent.
+            let ix = shift(ix).unwrap_or_else(|| {
+                self.selected_rows.insert(row_ix);
+                row_ix
+            });
+            self.selected_row = Some(ix);
+        }
+        self.selection_anchor = self.selection_anchor.map(|ix| shift(ix).unwrap_or(row_ix));
+        self.loading_rows.retain(|_, ix| match shift(*ix) {
+            Some(new_ix) => {
+                *ix = new_ix;
+                true
+            }
+            None => false,
+        });
+    }
+
+    /// Returns the active row if the table is a tree, and the selection state is Row.
+    fn selected_tree_row(&self) -> Option<usize> {
+        if !self.delegate.is_tree() || self.selection_state != SelectionState::Row {
+            return None;
+        }
+
+        self.selected_row
+    }
+
+    /// Collapse the active row, or select the parent row if it is already collapsed.
+    ///
+    /// Returns false if the table is not a tree.
+    fn collapse_selected_row(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        let Some(row_ix) = self.selected_tree_row() else {
+            return false;
+        };
+
+        if self.delegate.row_has_children(row_ix) && self.delegate.row_expanded(row_ix) {
+            self.set_row_expanded(row_ix, false, cx);
+        } else {
+            let depth = self.delegate.row_depth(row_ix);
+            if let Some(parent_ix) = (0..row_ix).rfind(|ix| self.delegate.row_depth(*ix) < depth) {
+                self.set_selected_row(parent_ix, cx);
+            }
+        }
+
+        true
+    }
+
+    /// Expand the active row, or select the first child row if it is already expanded.
+    ///
+    /// Returns false if the table is not a tree.
+    fn expand_selected_row(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        let Some(row_ix) = self.selected_tree_row() else {
+            return false;
+        };
+
+        if !self.delegate.row_has_children(row_ix) {
+            return true;
+        }
+
+        if !self.delegate.row_expanded(row_ix) {
+            self.set_row_expanded(row_ix, true, cx);
+        } else if row_ix + 1 < self.delegate.rows_count()
+            && self.delegate.row_depth(row_ix + 1) > self.delegate.row_depth(row_ix)
+        {
+            self.set_selected_row(row_ix + 1, cx);
+        }
+
+        true
+    }
+
+    fn emit_selected_rows(&mut self, cx: &mut ViewContext<Self>) {
+        cx.emit(TableEvent::SelectRows(
+            self.selected_rows.iter().copied().collect(),
+        ));
+        cx.notify();
+    }
+
+    /// Returns the prev or next row that can be selected from the active row.
+    fn next_selectable_row(&self, forward: bool) -> Option<usize> {
+        let rows_count = self.delegate.rows_count();
+        if rows_count == 0 {
+            return None;
+        }
+
+        let mut row_ix = self.selected_row;
+        for _ in 0..rows_count {
+            let next_ix = match row_ix {
+                None if forward => 0,
+                None => rows_count - 1,
+                Some(ix) if forward && ix + 1 < rows_count => ix + 1,
+                Some(ix) if !forward && ix > 0 => ix - 1,
+                Some(_) if self.delegate.can_loop_select() => {
+                    if forward {
+                        0
+                    } else {
+                        rows_count - 1
+                    }
+                }
+                Some(_) => return None,
+            };
+
+            if self.delegate.can_select_row(next_ix) {
+                return Some(next_ix);
+            }
+            row_ix = Some(next_ix);
+        }
+
+        None
     }
 
     fn set_selected_col(&mut self, col_ix: usize, cx: &mut ViewContext<Self>) {
         self.selection_state = SelectionState::Column;
         self.selected_col = Some(col_ix);
-        if let Some(col_ix) = self.selected_col {
-            self.horizontal_scroll_handle.scroll_to_item(col_ix);
+        let (left_cols, scroll_cols, _) = self.col_ranges();
+        if scroll_cols.contains(&col_ix) {
+            // The scrollable part only contains the non-fixed columns.
+            self.horizontal_scroll_handle
+                .scroll_to_item(col_ix - left_cols.len());
         }
         cx.emit(TableEvent::SelectCol(col_ix));
         cx.notify();
     }
 
-    fn on_row_click(&mut self, row_ix: usize, cx: &mut ViewContext<Self>) {
-        self.set_selected_row(row_ix, cx)
+    fn on_row_click(&mut self, row_ix: usize, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+        if event.modifiers.shift {
+            self.select_row_range(row_ix, cx)
+        } else if event.modifiers.control || event.modifiers.platform {
+            self.toggle_selected_row(row_ix, cx)
+        } else {
+            self.set_selected_row(row_ix, cx)
+        }
+    }
+
+    fn on_cell_mouse_down(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        event: &MouseDownEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        // Double click to edit the cell.
+        if event.click_count == 2 {
+            cx.stop_propagation();
+            self.edit_cell(row_ix, col_ix, cx);
+        }
     }
 
     fn on_col_head_click(&mut self, col_ix: usize, cx: &mut ViewContext<Self>) {
         self.set_selected_col(col_ix, cx)
     }
 
+    /// Start editing the cell at the given row and column, if the cell is editable.
+    pub fn edit_cell(&mut self, row_ix: usize, col_ix: usize, cx: &mut ViewContext<Self>) {
+        if !self.commit_editing(cx) {
+            return;
+        }
+
+        let Some(value) = self.delegate.cell_value(row_ix, col_ix) else {
+            return;
+        };
+
+        if self.selected_row != Some(row_ix) || self.selection_state != SelectionState::Row {
+            self.set_selected_row(row_ix, cx);
+        }
+        self.selected_col = Some(col_ix);
+
+        let (editor, subscription) = match value {
+            CellValue::Text(text) => {
+                let input = cx.new_view(|cx| {
+                    let mut input = TextInput::new(cx).small();
+                    input.set_text(text, cx);
+                    input
+                });
+                input.update(cx, |input, cx| input.focus(cx));
+                let subscription = cx.subscribe(&input, |this, _, event: &InputEvent, cx| {
+                    if let InputEvent::PressEnter = event {
+                        this.commit_editing(cx);
+                    }
+                });
+                (CellEditor::Text(input), Some(subscription))
+            }
+            CellValue::Bool(checked) => (CellEditor::Bool(checked), None),
+            CellValue::Select(options, selected_ix) => {
+                let dropdown = cx.new_view(|cx| {
+                    Dropdown::new(
+                        "table-cell-dropdown",
+                        SearchableVec::new(options.clone()),
+                        selected_ix,
+                        cx,
+                    )
+                    .small()
+                });
+                dropdown.update(cx, |dropdown, cx| dropdown.focus(cx));
+                let subscription = cx.subscribe(
+                    &dropdown,
+                    |this, _, _: &DropdownEvent<SearchableVec<SharedString>>, cx| {
+                        this.commit_editing(cx);
+                    },
+                );
+                (CellEditor::Select(options, dropdown), Some(subscription))
+            }
+        };
+
+        self.editing = Some(EditingCell {
+            row_ix,
+            col_ix,
+            editor,
+            error: None,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    /// Commit the editing cell to the delegate, returns false if the value is rejected.
+    fn commit_editing(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        let Some(mut editing) = self.editing.take() else {
+            return true;
+        };
+
+        let value = editing.value(cx);
+        match self
+            .delegate
+            .commit_cell(editing.row_ix, editing.col_ix, value, cx)
+        {
+            Ok(()) => {
+                self.focus_handle.focus(cx);
+                cx.notify();
+                true
+            }
+            Err(err) => {
+                editing.error = Some(err);
+                self.editing = Some(editing);
+                cx.notify();
+                false
+            }
+        }
+    }
+
+    /// Cancel the editing cell without commit, returns false if there is no editing cell.
+    fn cancel_editing(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        if self.editing.take().is_none() {
+            return false;
+        }
+
+        self.focus_handle.focus(cx);
+        cx.notify();
+        true
+    }
+
+    fn toggle_editing_checkbox(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(EditingCell {
+            editor: CellEditor::Bool(checked),
+            ..
+        }) = self.editing.as_mut()
+        {
+            *checked = !*checked;
+            self.commit_editing(cx);
+        }
+    }
+
+    /// Returns the prev or next editable cell from the given cell, in the order of rows then columns.
+    fn next_editable_cell(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        forward: bool,
+    ) -> Option<(usize, usize)> {
+        let cols_count = self.delegate.cols_count();
+        let rows_count = self.delegate.rows_count();
+        let mut ix = row_ix * cols_count + col_ix;
+
+        loop {
+            ix = if forward { ix + 1 } else { ix.checked_sub(1)? };
+            if ix >= rows_count * cols_count {
+                return None;
+            }
+
+            let (row_ix, col_ix) = (ix / cols_count, ix % cols_count);
+            if self.delegate.cell_value(row_ix, col_ix).is_some() {
+                return Some((row_ix, col_ix));
+            }
+        }
+    }
+
+    fn edit_next_cell(&mut self, forward: bool, cx: &mut ViewContext<Self>) {
+        let Some((row_ix, col_ix)) = self.editing.as_ref().map(|e| (e.row_ix, e.col_ix)) else {
+            return;
+        };
+
+        if !self.commit_editing(cx) {
+            return;
+        }
+
+        if let Some((row_ix, col_ix)) = self.next_editable_cell(row_ix, col_ix, forward) {
+            self.edit_cell(row_ix, col_ix, cx);
+        }
+    }
+
+    fn action_edit_cell(&mut self, _: &EditCell, cx: &mut ViewContext<Self>) {
+        if self.editing.is_some() {
+            self.toggle_editing_checkbox(cx);
+            return;
+        }
+
+        let Some(row_ix) = self.selected_row else {
+            return;
+        };
+
+        let col_ix = self
+            .selected_col
+            .filter(|col_ix| self.delegate.cell_value(row_ix, *col_ix).is_some())
+            .or_else(|| {
+                (0..self.delegate.cols_count())
+                    .find(|col_ix| self.delegate.cell_value(row_ix, *col_ix).is_some())
+            });
+        if let Some(col_ix) = col_ix {
+            self.edit_cell(row_ix, col_ix, cx);
+        }
+    }
+
+    fn action_edit_next_cell(&mut self, _: &EditNextCell, cx: &mut ViewContext<Self>) {
+        self.edit_next_cell(true, cx);
+    }
+
+    fn action_edit_prev_cell(&mut self, _: &EditPrevCell, cx: &mut ViewContext<Self>) {
+        self.edit_next_cell(false, cx);
+    }
+
     fn action_cancel(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
+        if self.cancel_editing(cx) {
+            return;
+        }
+
         self.selection_state = SelectionState::Row;
         self.selected_row = None;
         self.selected_col = None;
+        self.selection_anchor = None;
+        if !self.selected_rows.is_empty() {
+            self.selected_rows.clear();
+            self.emit_selected_rows(cx);
+        }
         cx.notify();
     }
 
     fn action_select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
-        let mut selected_row = self.selected_row.unwrap_or(0);
-        let rows_count = self.delegate.rows_count();
-        if selected_row > 0 {
-            selected_row = selected_row - 1;
-        } else {
-            if self.delegate.can_loop_select() {
-                selected_row = rows_count - 1;
-            }
+        if let Some(row_ix) = self.next_selectable_row(false) {
+            self.set_selected_row(row_ix, cx);
         }
-
-        self.set_selected_row(selected_row, cx);
     }
 
     fn action_select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
-        let mut selected_row = self.selected_row.unwrap_or(0);
-        if selected_row < self.delegate.rows_count() - 1 {
-            selected_row += 1;
-        } else {
-            if self.delegate.can_loop_select() {
-                selected_row = 0;
-            }
+        if let Some(row_ix) = self.next_selectable_row(true) {
+            self.set_selected_row(row_ix, cx);
+        }
+    }
+
+    fn action_select_prev_extend(&mut self, _: &SelectPrevExtend, cx: &mut ViewContext<Self>) {
+        if let Some(row_ix) = self.next_selectable_row(false) {
+            self.select_row_range(row_ix, cx);
+        }
+    }
+
+    fn action_select_next_extend(&mut self, _: &SelectNextExtend, cx: &mut ViewContext<Self>) {
+        if let Some(row_ix) = self.next_selectable_row(true) {
+            self.select_row_range(row_ix, cx);
         }
+    }
 
-        self.set_selected_row(selected_row, cx);
+    fn action_select_all(&mut self, _: &SelectAll, cx: &mut ViewContext<Self>) {
+        self.select_all(cx);
     }
 
     fn action_select_prev_col(&mut self, _: &SelectPrevColumn, cx: &mut ViewContext<Self>) {
+        if self.collapse_selected_row(cx) {
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
         let cols_count = self.delegate.cols_count();
         if selected_col > 0 {
@@ -306,6 +987,10 @@ where
     }
 
     fn action_select_next_col(&mut self, _: &SelectNextColumn, cx: &mut ViewContext<Self>) {
+        if self.expand_selected_row(cx) {
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
         if selected_col < self.delegate.cols_count() - 1 {
             selected_col += 1;
@@ -329,6 +1014,66 @@ where
             .px_2()
     }
 
+    /// Returns the column ranges of the fixed left part, the scrollable part and the fixed right part.
+    fn col_ranges(&self) -> (Range<usize>, Range<usize>, Range<usize>) {
+        let cols_count = self.col_groups.len();
+        let left = self.delegate.fixed_left_cols_count().min(cols_count);
+        let right = self
+            .delegate
+            .fixed_right_cols_count()
+            .min(cols_count - left);
+
+        (
+            0..left,
+            left..cols_count - right,
+            cols_count - right..cols_count,
+        )
+    }
+
+    /// Layout the cells of a row into the fixed left part, the `scrollable` part and the fixed right part.
+    fn render_row_parts<E: IntoElement>(
+        &self,
+        scrollable: impl ParentElement + Styled + IntoElement,
+        border_color: Hsla,
+        mut render_col: impl FnMut(usize) -> E,
+    ) -> [AnyElement; 3] {
+        let (left_cols, scroll_cols, right_cols) = self.col_ranges();
+
+        [
+            h_flex()
+                .h_full()
+                .flex_shrink_0()
+                .when(!left_cols.is_empty(), |this| {
+                    this.border_r_1().border_color(border_color)
+                })
+                .children(left_cols.map(&mut render_col))
+                .into_any_element(),
+            scrollable
+                .flex_1()
+                .h_full()
+                .children(scroll_cols.map(&mut render_col))
+                .child(h_flex().w(px(100.)).h_full().flex_shrink_0())
+                .into_any_element(),
+            h_flex()
+                .h_full()
+                .flex_shrink_0()
+                .when(!right_cols.is_empty(), |this| {
+                    this.border_l_1().border_color(border_color)
+                })
+                .children(right_cols.map(&mut render_col))
+                .into_any_element(),
+        ]
+    }
+
+    /// Make the cell of the scrollable columns sync with the `horizontal_scroll_handle`.
+    fn scroll_offset(&self, col_ix: usize) -> Pixels {
+        if self.col_ranges().1.contains(&col_ix) {
+            self.horizontal_scroll_handle.offset().x
+        } else {
+            px(0.)
+        }
+    }
+
     /// Show Column selection style, when the column is selected and the selection state is Column.
     fn col_wrap(&self, col_ix: usize, cx: &mut ViewContext<Self>) -> Div {
         if self.selected_col == Some(col_ix) && self.selection_state == SelectionState::Column {
@@ -338,6 +1083,103 @@ where
         }
     }
 
+    /// Render the cell at the given row and column, or the editor if the cell is editing.
+    fn render_td(&self, row_ix: usize, col_ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let cell = match self
+            .editing
+            .as_ref()
+            .filter(|editing| editing.row_ix == row_ix && editing.col_ix == col_ix)
+        {
+            Some(editing) => self.render_cell_editor(editing, cx).into_any_element(),
+            None => self
+                .delegate
+                .render_td(row_ix, col_ix, cx)
+                .into_any_element(),
+        };
+
+        if col_ix > 0 || !self.delegate.is_tree() {
+            return cell;
+        }
+
+        h_flex()
+            .gap_1()
+            .items_center()
+            .pl(px(16.) * self.delegate.row_depth(row_ix) as f32)
+            .child(self.render_tree_toggle(row_ix, cx))
+            .child(cell)
+            .into_any_element()
+    }
+
+    /// Render the expand/collapse toggle of the row, or an empty placeholder if the row has no children.
+    fn render_tree_toggle(&self, row_ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let toggle = h_flex()
+            .id(("tree-toggle", row_ix))
+            .flex_shrink_0()
+            .size_4()
+            .justify_center()
+            .items_center();
+
+        if self.is_loading_row(row_ix) {
+            return toggle.child(Indicator::new().xsmall());
+        }
+
+        if !self.delegate.row_has_children(row_ix) {
+            return toggle;
+        }
+
+        let icon = if self.delegate.row_expanded(row_ix) {
+            IconName::ChevronDown
+        } else {
+            IconName::ChevronRight
+        };
+
+        toggle
+            .cursor_pointer()
+            .rounded_sm()
+            .hover(|this| this.bg(cx.theme().secondary))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, cx| {
+                    cx.stop_propagation();
+                    this.toggle_row_expanded(row_ix, cx);
+                }),
+            )
+            .child(
+                Icon::new(icon)
+                    .size_3()
+                    .text_color(cx.theme().muted_foreground),
+            )
+    }
+
+    fn render_cell_editor(&self, editing: &EditingCell, cx: &mut ViewContext<Self>) -> Div {
+        h_flex()
+            .w_full()
+            .gap_1()
+            .items_center()
+            // Prevent the row click to commit the editing.
+            .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+            .map(|this| match &editing.editor {
+                CellEditor::Text(input) => this.child(input.clone()),
+                CellEditor::Bool(checked) => this.child(
+                    Checkbox::new("table-cell-checkbox")
+                        .checked(*checked)
+                        .on_click(
+                            cx.listener(|this, _: &Selection, cx| this.toggle_editing_checkbox(cx)),
+                        ),
+                ),
+                CellEditor::Select(_, dropdown) => this.child(dropdown.clone()),
+            })
+            .when_some(editing.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .text_xs()
+                        .text_color(cx.theme().destructive)
+                        .child(error),
+                )
+            })
+    }
+
     fn render_scrollbar(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
         let view = cx.view().clone();
         let state = self.scrollbar_state.clone();
@@ -405,7 +1247,11 @@ where
                         let ix = *ix;
                         view.resizing_col = Some(ix);
 
-                        let col_group = *view.col_groups.get(ix).expect("BUG: invalid col index");
+                        let col_group = view
+                            .col_groups
+                            .get(ix)
+                            .cloned()
+                            .expect("BUG: invalid col index");
 
                         view.resize_cols(
                             ix,
@@ -414,7 +1260,9 @@ where
                         );
 
                         // scroll the table if the drag is near the edge
-                        view.scroll_table_by_col_resizing(e.event.position, col_group, cx);
+                        if view.col_ranges().1.contains(&ix) {
+                            view.scroll_table_by_col_resizing(e.event.position, col_group, cx);
+                        }
                     }
                 };
             }))
@@ -497,6 +1345,7 @@ where
         }
 
         let sort = sort.unwrap();
+        self.cancel_editing(cx);
         let sort = match sort {
             ColSort::Ascending => ColSort::Descending,
             ColSort::Descending => ColSort::Ascending,
@@ -579,7 +1428,12 @@ where
                             .justify_between()
                             .items_center()
                             .child(self.delegate.render_th(col_ix, cx))
-                            .children(self.render_sort_icon(col_ix, cx)),
+                            .child(
+                                h_flex()
+                                    .items_center()
+                                    .children(self.render_sort_icon(col_ix, cx))
+                                    .children(self.render_filter_icon(col_ix, cx)),
+                            ),
                     )
                     .when(self.delegate.can_move_col(col_ix), |this| {
                         this.on_drag(
@@ -626,11 +1480,190 @@ where
             })
     }
 
+    /// Render the footer row, it is pinned at the bottom of the table and scrolls horizontally with the rows.
+    fn render_tfoot(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let border_color = cx.theme().border;
+
+        h_flex()
+            .id("table-foot")
+            .w_full()
+            .h_10()
+            .flex_shrink_0()
+            .border_t_1()
+            .border_color(border_color)
+            .bg(cx.theme().table_head)
+            .children(
+                self.render_row_parts(h_flex().overflow_hidden(), border_color, |col_ix| {
+                    h_flex().left(self.scroll_offset(col_ix)).child(
+                        self.render_cell(col_ix, cx)
+                            .flex_shrink_0()
+                            .child(self.delegate.render_tf(col_ix, cx)),
+                    )
+                }),
+            )
+    }
+
+    /// Copy the selected rows, or the selected column to the clipboard in the given format.
+    pub fn copy_selection(&self, format: TableFormat, cx: &mut WindowContext) {
+        let (rows, cols): (Vec<usize>, Vec<usize>) = match self.selection_state {
+            SelectionState::Row => (
+                self.selected_rows.iter().copied().collect(),
+                (0..self.delegate.cols_count()).collect(),
+            ),
+            SelectionState::Column => match self.selected_col {
+                Some(col_ix) => ((0..self.delegate.rows_count()).collect(), vec![col_ix]),
+                None => return,
+            },
+        };
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut buf = Vec::new();
+        for row_ix in rows {
+            let cells = cols
+                .iter()
+                .map(|col_ix| self.delegate.cell_text(row_ix, *col_ix));
+            format
+                .write_row(&mut buf, cells)
+                .expect("BUG: failed to write into the buffer");
+        }
+
+        let text = String::from_utf8_lossy(&buf);
+        // Avoid the extra empty row when pasting.
+        let text = text.strip_suffix('\n').unwrap_or(&text);
+        cx.write_to_clipboard(ClipboardItem::new(text.to_string()));
+    }
+
+    /// Export the header and all the rows of the table to the `writer` in the given format.
+    pub fn export(&self, format: TableFormat, writer: &mut impl Write) -> io::Result<()> {
+        let cols_count = self.delegate.cols_count();
+
+        format.write_row(
+            writer,
+            (0..cols_count).map(|col_ix| self.delegate.col_name(col_ix)),
+        )?;
+        for row_ix in 0..self.delegate.rows_count() {
+            format.write_row(
+                writer,
+                (0..cols_count).map(|col_ix| self.delegate.cell_text(row_ix, col_ix)),
+            )?;
+        }
+
+        writer.flush()
+    }
+
+    fn action_copy(&mut self, _: &Copy, cx: &mut ViewContext<Self>) {
+        self.copy_selection(TableFormat::Tsv, cx);
+    }
+
+    fn action_copy_as_csv(&mut self, _: &CopyAsCsv, cx: &mut ViewContext<Self>) {
+        self.copy_selection(TableFormat::Csv, cx);
+    }
+
+    /// Set the filter of the column at the given index, `None` to clear the filter.
+    pub fn set_col_filter(
+        &mut self,
+        col_ix: usize,
+        filter: Option<FilterValue>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+        if col_group.filter == filter {
+            return;
+        }
+
+        col_group.filter = filter;
+        self.perform_filter(cx);
+    }
+
+    fn perform_filter(&mut self, cx: &mut ViewContext<Self>) {
+        self.cancel_editing(cx);
+        let filters = self
+            .col_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, col_group)| Some((ix, col_group.filter.clone()?)))
+            .collect::<Vec<_>>();
+        self.delegate.perform_filter(&filters, cx);
+
+        // The rows are re-queried, the selected rows are no longer valid.
+        self.selected_row = None;
+        self.selection_anchor = None;
+        self.loading_rows.clear();
+        self.rows_count = self.delegate.rows_count();
+        if !self.selected_rows.is_empty() {
+            self.selected_rows.clear();
+            self.emit_selected_rows(cx);
+        }
+        self.vertical_scroll_handle.scroll_to_item(0);
+        cx.notify();
+    }
+
+    /// Render the filter button of the column, with a dot when the filter is active.
+    fn render_filter_icon(
+        &self,
+        col_ix: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let filter = self.delegate.col_filter(col_ix)?;
+        let value = self.col_groups.get(col_ix).and_then(|g| g.filter.clone());
+        let is_active = value.is_some();
+        let view = cx.view().clone();
+
+        Some(
+            div()
+                .relative()
+                .ml_1()
+                .child(
+                    Popover::new(("col-filter", col_ix))
+                        .anchor(AnchorCorner::TopRight)
+                        .trigger(
+                            Button::new(("col-filter", col_ix), cx)
+                                .icon(IconName::Filter)
+                                .ghost()
+                                .xsmall(),
+                        )
+                        .content(move |cx| {
+                            let view = view.clone();
+                            let filter = filter.clone();
+                            let value = value.clone();
+                            cx.new_view(|cx| {
+                                FilterPanel::new(
+                                    filter,
+                                    value,
+                                    move |value, cx| {
+                                        view.update(cx, |table, cx| {
+                                            table.set_col_filter(col_ix, value, cx)
+                                        })
+                                    },
+                                    cx,
+                                )
+                            })
+                        }),
+                )
+                .when(is_active, |this| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .right_0()
+                            .size_1p5()
+                            .rounded_full()
+                            .bg(cx.theme().primary),
+                    )
+                }),
+        )
+    }
+
     fn move_col(&mut self, col_ix: usize, to_ix: usize, cx: &mut ViewContext<Self>) {
         if col_ix == to_ix {
             return;
         }
 
+        self.cancel_editing(cx);
         self.delegate.move_col(col_ix, to_ix);
         let col_group = self.col_groups.remove(col_ix);
         self.col_groups.insert(to_ix, col_group);
@@ -658,24 +1691,33 @@ where
         let view = cx.view().clone();
         let vertical_scroll_handle = self.vertical_scroll_handle.clone();
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
-        let cols_count: usize = self.delegate.cols_count();
         let rows_count = self.delegate.rows_count();
 
-        fn last_empty_col(_: &mut WindowContext) -> Div {
-            h_flex().w(px(100.)).h_full().flex_shrink_0()
-        }
-
         fn tr(_: &mut WindowContext) -> Div {
             h_flex()
         }
 
+        let mut key_context = KeyContext::default();
+        key_context.add(CONTEXT);
+        if self.editing.is_some() {
+            key_context.add(EDITING);
+        }
+
         let inner_table = v_flex()
-            .key_context("Table")
+            .key_context(key_context)
             .id("table")
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::action_cancel))
+            .on_action(cx.listener(Self::action_edit_cell))
+            .on_action(cx.listener(Self::action_edit_next_cell))
+            .on_action(cx.listener(Self::action_edit_prev_cell))
             .on_action(cx.listener(Self::action_select_next))
             .on_action(cx.listener(Self::action_select_prev))
+            .on_action(cx.listener(Self::action_select_next_extend))
+            .on_action(cx.listener(Self::action_select_prev_extend))
+            .on_action(cx.listener(Self::action_select_all))
+            .on_action(cx.listener(Self::action_copy))
+            .on_action(cx.listener(Self::action_copy_as_csv))
             .on_action(cx.listener(Self::action_select_next_col))
             .on_action(cx.listener(Self::action_select_prev_col))
             .size_full()
@@ -692,21 +1734,22 @@ where
                             let horizontal_scroll_handle = horizontal_scroll_handle.clone();
                             move |table, _, cx| {
                                 // Columns
+                                let border_color = cx.theme().border;
                                 tr(cx)
                                     .id("table-head")
                                     .w_full()
                                     .h_10()
-                                    .overflow_scroll()
-                                    .track_scroll(&horizontal_scroll_handle)
                                     .bg(cx.theme().table_head)
                                     .children(
-                                        table
-                                            .col_groups
-                                            .iter()
-                                            .enumerate()
-                                            .map(|(col_ix, _)| table.render_th(col_ix, cx)),
+                                        table.render_row_parts(
+                                            h_flex()
+                                                .id("table-head-scrollable")
+                                                .overflow_scroll()
+                                                .track_scroll(&horizontal_scroll_handle),
+                                            border_color,
+                                            |col_ix| table.render_th(col_ix, cx),
+                                        ),
                                     )
-                                    .child(last_empty_col(cx))
                                     .map(|this| vec![this])
                             }
                         })
@@ -716,8 +1759,8 @@ where
             .child(
                 h_flex().id("table-body").flex_grow().size_full().child(
                     uniform_list(view, "table-uniform-list", rows_count, {
-                        let horizontal_scroll_handle = horizontal_scroll_handle.clone();
                         move |table, visible_range, cx| {
+                            let border_color = cx.theme().border;
                             visible_range
                                 .map(|row_ix| {
                                     table
@@ -732,40 +1775,47 @@ where
                                             this.bg(cx.theme().table_even)
                                         })
                                         .hover(|this| {
-                                            if table.selected_row == Some(row_ix) {
+                                            if table.selected_rows.contains(&row_ix) {
                                                 this
                                             } else {
                                                 this.bg(cx.theme().table_hover)
                                             }
                                         })
-                                        .children((0..cols_count).map(|col_ix| {
-                                            table
-                                                .col_wrap(col_ix, cx) // Make the row scroll sync with the horizontal_scroll_handle to support horizontal scrolling.
-                                                .left(horizontal_scroll_handle.offset().x)
-                                                .child(
-                                                    table
-                                                        .render_cell(col_ix, cx)
-                                                        .flex_shrink_0()
-                                                        .child(
-                                                            table
-                                                                .delegate
-                                                                .render_td(row_ix, col_ix, cx),
-                                                        ),
-                                                )
-                                        }))
-                                        .child(last_empty_col(cx))
+                                        .children(table.render_row_parts(
+                                            h_flex().overflow_hidden(),
+                                            border_color,
+                                            |col_ix| {
+                                                table
+                                                    .col_wrap(col_ix, cx)
+                                                    .left(table.scroll_offset(col_ix))
+                                                    .child(
+                                                        table
+                                                            .render_cell(col_ix, cx)
+                                                            .flex_shrink_0()
+                                                            .on_mouse_down(
+                                                                MouseButton::Left,
+                                                                cx.listener(move |this, e, cx| {
+                                                                    this.on_cell_mouse_down(
+                                                                        row_ix, col_ix, e, cx,
+                                                                    )
+                                                                }),
+                                                            )
+                                                            .child(
+                                                                table.render_td(row_ix, col_ix, cx),
+                                                            ),
+                                                    )
+                                            },
+                                        ))
                                         // Row selected style
-                                        .when_some(table.selected_row, |this, selected_row| {
-                                            this.when(
-                                                row_ix == selected_row
-                                                    && table.selection_state == SelectionState::Row,
-                                                |this| this.bg(cx.theme().table_active),
-                                            )
-                                        })
+                                        .when(
+                                            table.selected_rows.contains(&row_ix)
+                                                && table.selection_state == SelectionState::Row,
+                                            |this| this.bg(cx.theme().table_active),
+                                        )
                                         .on_mouse_down(
                                             MouseButton::Left,
-                                            cx.listener(move |this, _, cx| {
-                                                this.on_row_click(row_ix, cx);
+                                            cx.listener(move |this, e: &MouseDownEvent, cx| {
+                                                this.on_row_click(row_ix, e, cx);
                                             }),
                                         )
                                 })
@@ -778,7 +1828,10 @@ where
                     .track_scroll(vertical_scroll_handle)
                     .into_any_element(),
                 ),
-            );
+            )
+            .when(self.delegate.has_foot(), |this| {
+                this.child(self.render_tfoot(cx))
+            });
 
         let view = cx.view().clone();
         div()
@@ -0,0 +1,69 @@
+use std::io::{self, Write};
+
+/// The text format to copy or export the table cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// Tab-separated values, can be pasted into the spreadsheets directly.
+    Tsv,
+    /// Comma-separated values.
+    Csv,
+}
+
+impl TableFormat {
+    fn separator(&self) -> char {
+        match self {
+            Self::Tsv => '\t',
+            Self::Csv => ',',
+        }
+    }
+
+    /// Write the cells as a line, the cell will be quoted if it contains the separator, quotes or newlines.
+    pub(super) fn write_row<S: AsRef<str>>(
+        &self,
+        writer: &mut impl Write,
+        cells: impl IntoIterator<Item = S>,
+    ) -> io::Result<()> {
+        let separator = self.separator();
+
+        for (ix, cell) in cells.into_iter().enumerate() {
+            if ix > 0 {
+                write!(writer, "{}", separator)?;
+            }
+
+            let cell = cell.as_ref();
+            if cell.contains([separator, '"', '\n', '\r']) {
+                write!(writer, "\"{}\"", cell.replace('"', "\"\""))?;
+            } else {
+                write!(writer, "{}", cell)?;
+            }
+        }
+
+        writeln!(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TableFormat;
+
+    #[test]
+    fn test_write_row() {
+        #[track_caller]
+        fn assert_row(format: TableFormat, cells: &[&str], expected: &str) {
+            let mut buf = Vec::new();
+            format.write_row(&mut buf, cells).unwrap();
+            assert_eq!(String::from_utf8(buf).unwrap(), expected);
+        }
+
+        assert_row(TableFormat::Tsv, &["a", "b", "c"], "a\tb\tc\n");
+        assert_row(TableFormat::Tsv, &["a,b", "c\td"], "a,b\t\"c\td\"\n");
+        assert_row(TableFormat::Csv, &["a", "b", "c"], "a,b,c\n");
+        assert_row(TableFormat::Csv, &["a,b", "c\td"], "\"a,b\",c\td\n");
+        assert_row(
+            TableFormat::Csv,
+            &["say \"hi\"", "1\n2"],
+            "\"say \"\"hi\"\"\",\"1\n2\"\n",
+        );
+        assert_row(TableFormat::Csv, &[], "\n");
+    }
+}
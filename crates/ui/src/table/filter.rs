@@ -0,0 +1,223 @@
+use std::rc::Rc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AppContext, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, InteractiveElement as _, IntoElement, ParentElement, Render, SharedString,
+    Styled, Subscription, View, ViewContext, VisualContext as _, WindowContext,
+};
+
+use crate::{
+    button::Button,
+    checkbox::Checkbox,
+    h_flex,
+    input::{InputEvent, TextInput},
+    theme::ActiveTheme,
+    v_flex, Clickable as _, Selection, Sizable as _,
+};
+
+/// The filter UI of a column, returned by `TableDelegate::col_filter`.
+#[derive(Debug, Clone)]
+pub enum ColFilter {
+    /// Filter the rows by a text.
+    Text,
+    /// Filter the rows by a checklist of the options.
+    Enum(Vec<SharedString>),
+    /// Filter the rows by a number range.
+    Range,
+}
+
+/// The active filter of a column, reported to `TableDelegate::perform_filter`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Text(SharedString),
+    /// The indexes of the checked options.
+    Enum(Vec<usize>),
+    /// The min and max of the range, both are inclusive.
+    Range(Option<f64>, Option<f64>),
+}
+
+impl FilterValue {
+    /// Returns true if the filter will not filter out any rows, e.g.: the text is empty or no option is checked.
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) => text.is_empty(),
+            Self::Enum(checked) => checked.is_empty(),
+            Self::Range(min, max) => min.is_none() && max.is_none(),
+        }
+    }
+}
+
+enum FilterEditor {
+    Text(View<TextInput>),
+    Enum(Vec<SharedString>, Vec<usize>),
+    Range(View<TextInput>, View<TextInput>),
+}
+
+/// The content of the column filter popover.
+pub(super) struct FilterPanel {
+    focus_handle: FocusHandle,
+    editor: FilterEditor,
+    on_change: Rc<dyn Fn(Option<FilterValue>, &mut WindowContext)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FilterPanel {
+    pub(super) fn new(
+        filter: ColFilter,
+        value: Option<FilterValue>,
+        on_change: impl Fn(Option<FilterValue>, &mut WindowContext) + 'static,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let mut subscriptions = Vec::new();
+
+        let editor = match filter {
+            ColFilter::Text => {
+                let text = match value {
+                    Some(FilterValue::Text(text)) => text,
+                    _ => SharedString::default(),
+                };
+                let input = Self::new_input("Contains...", text, &mut subscriptions, cx);
+                FilterEditor::Text(input)
+            }
+            ColFilter::Enum(options) => {
+                let checked = match value {
+                    Some(FilterValue::Enum(checked)) => checked,
+                    _ => vec![],
+                };
+                FilterEditor::Enum(options, checked)
+            }
+            ColFilter::Range => {
+                let (min, max) = match value {
+                    Some(FilterValue::Range(min, max)) => (min, max),
+                    _ => (None, None),
+                };
+                let format = |v: Option<f64>| v.map(|v| v.to_string()).unwrap_or_default();
+                let min = Self::new_input("Min", format(min).into(), &mut subscriptions, cx);
+                let max = Self::new_input("Max", format(max).into(), &mut subscriptions, cx);
+                FilterEditor::Range(min, max)
+            }
+        };
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            editor,
+            on_change: Rc::new(on_change),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn new_input(
+        placeholder: &'static str,
+        text: SharedString,
+        subscriptions: &mut Vec<Subscription>,
+        cx: &mut ViewContext<Self>,
+    ) -> View<TextInput> {
+        let input = cx.new_view(|cx| {
+            let mut input = TextInput::new(cx)
+                .placeholder(placeholder)
+                .cleanable(true)
+                .small();
+            input.set_text(text, cx);
+            input
+        });
+
+        subscriptions.push(
+            cx.subscribe(&input, |this, _, event: &InputEvent, cx| match event {
+                InputEvent::Change(_) => this.apply(cx),
+                InputEvent::PressEnter => cx.emit(DismissEvent),
+                _ => {}
+            }),
+        );
+
+        input
+    }
+
+    fn value(&self, cx: &AppContext) -> Option<FilterValue> {
+        let parse = |input: &View<TextInput>| input.read(cx).text().trim().parse::<f64>().ok();
+
+        let value = match &self.editor {
+            FilterEditor::Text(input) => FilterValue::Text(input.read(cx).text()),
+            FilterEditor::Enum(_, checked) => FilterValue::Enum(checked.clone()),
+            FilterEditor::Range(min, max) => FilterValue::Range(parse(min), parse(max)),
+        };
+
+        Some(value).filter(|value| !value.is_empty())
+    }
+
+    fn apply(&mut self, cx: &mut ViewContext<Self>) {
+        let value = self.value(cx);
+        (self.on_change)(value, cx);
+        cx.notify();
+    }
+
+    fn toggle_option(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let FilterEditor::Enum(_, checked) = &mut self.editor {
+            match checked.iter().position(|checked_ix| *checked_ix == ix) {
+                Some(pos) => {
+                    checked.remove(pos);
+                }
+                None => {
+                    checked.push(ix);
+                    checked.sort();
+                }
+            }
+        }
+
+        self.apply(cx);
+    }
+
+    fn clear(&mut self, cx: &mut ViewContext<Self>) {
+        (self.on_change)(None, cx);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for FilterPanel {}
+
+impl FocusableView for FilterPanel {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        match &self.editor {
+            FilterEditor::Text(input) | FilterEditor::Range(input, _) => input.focus_handle(cx),
+            FilterEditor::Enum(..) => self.focus_handle.clone(),
+        }
+    }
+}
+
+impl Render for FilterPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .p_3()
+            .gap_3()
+            .w(px(220.))
+            .map(|this| match &self.editor {
+                FilterEditor::Text(input) => this.child(input.clone()),
+                FilterEditor::Enum(options, checked) => this.child(v_flex().gap_2().children(
+                    options.iter().enumerate().map(|(ix, option)| {
+                        Checkbox::new(("filter-option", ix))
+                            .label(option.clone())
+                            .checked(checked.contains(&ix))
+                            .on_click(cx.listener(move |this, _: &Selection, cx| {
+                                this.toggle_option(ix, cx)
+                            }))
+                    }),
+                )),
+                FilterEditor::Range(min, max) => this.child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(min.clone())
+                        .child(div().text_color(cx.theme().muted_foreground).child("-"))
+                        .child(max.clone()),
+                ),
+            })
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("filter-clear", cx)
+                        .label("Clear")
+                        .small()
+                        .on_click(cx.listener(|this, _, cx| this.clear(cx))),
+                ),
+            )
+    }
+}
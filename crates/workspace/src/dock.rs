@@ -326,6 +326,12 @@ impl Dock {
         }
     }
 
+    pub fn panel_index_for_persistent_name(&self, name: &str) -> Option<usize> {
+        self.panel_entries
+            .iter()
+            .position(|entry| entry.panel.persistent_name() == name)
+    }
+
     pub fn panels_len(&self) -> usize {
         self.panel_entries.len()
     }
@@ -1,13 +1,17 @@
-use std::any::TypeId;
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
 
+use anyhow::{anyhow, Result};
 use gpui::{
     AnyElement, AnyView, AppContext, Element as _, Entity as _, EntityId, EventEmitter,
-    FocusHandle, FocusableView, Pixels, Point, SharedString, View, ViewContext, WeakView,
+    FocusHandle, FocusableView, Pixels, Point, SharedString, Task, View, ViewContext, WeakView,
     WindowContext,
 };
 
 use super::{
-    pane::{self, Pane},
+    pane::{self, NavHistory, Pane},
     workspace::{Workspace, WorkspaceId},
 };
 
@@ -24,6 +28,30 @@ pub struct TabContentParams {
     pub selected: bool,
 }
 
+/// The navigation history of an item, to push the entries of the item into the pane history.
+#[derive(Clone)]
+pub struct ItemNavHistory {
+    history: NavHistory,
+    item: Arc<dyn WeakItemHandle>,
+}
+
+impl ItemNavHistory {
+    pub(crate) fn new(history: NavHistory, item: Box<dyn WeakItemHandle>) -> Self {
+        Self {
+            history,
+            item: Arc::from(item),
+        }
+    }
+
+    /// Push an entry for this item, the `data` will be passed to `Item::navigate` when navigating back or forward to it.
+    pub fn push<D: Any + Send + Sync>(&self, data: Option<D>) {
+        self.history.push(
+            self.item.clone(),
+            data.map(|data| Arc::new(data) as Arc<dyn Any + Send + Sync>),
+        );
+    }
+}
+
 pub trait Item: FocusableView + EventEmitter<Self::Event> {
     type Event;
 
@@ -79,9 +107,47 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
     }
 
     fn added_to_workspace(&mut self, _workspace: &mut Workspace, _cx: &mut ViewContext<Self>) {}
+
+    /// Invoked when the item is added to a pane, keep the `history` to push the entries of the item.
+    fn set_nav_history(&mut self, _history: ItemNavHistory, _cx: &mut ViewContext<Self>) {}
+
+    /// Restore the item to the state of the `data` pushed by `ItemNavHistory::push`.
+    ///
+    /// Returns `true` if the state of the item is changed.
+    fn navigate(&mut self, _data: &dyn Any, _cx: &mut ViewContext<Self>) -> bool {
+        false
+    }
+
     fn pixel_position_of_cursor(&self, _: &AppContext) -> Option<Point<Pixels>> {
         None
     }
+
+    /// Returns the unique kind of the item to save it in the workspace layout, default is `None` to skip.
+    ///
+    /// The item type also must be registered by `persistence::register_serializable_item`.
+    fn serialized_item_kind() -> Option<&'static str>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Returns the state of the item to save, return `None` to skip this item.
+    fn serialize(&self, _cx: &AppContext) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Restore the item from the state returned by `serialize`.
+    fn deserialize(
+        _workspace: WeakView<Workspace>,
+        _state: serde_json::Value,
+        _cx: &mut WindowContext,
+    ) -> Task<Result<View<Self>>>
+    where
+        Self: Sized,
+    {
+        Task::ready(Err(anyhow!("This item does not support deserialization")))
+    }
 }
 
 pub trait ItemHandle: 'static + Send {
@@ -108,6 +174,8 @@ pub trait ItemHandle: 'static + Send {
         cx: &mut ViewContext<Workspace>,
     );
     fn deactivated(&self, cx: &mut WindowContext);
+    fn set_nav_history(&self, history: ItemNavHistory, cx: &mut WindowContext);
+    fn navigate(&self, data: &dyn Any, cx: &mut WindowContext) -> bool;
     fn workspace_deactivated(&self, cx: &mut WindowContext);
     fn to_any(&self) -> AnyView;
     fn on_release(
@@ -119,6 +187,7 @@ pub trait ItemHandle: 'static + Send {
     fn downgrade_item(&self) -> Box<dyn WeakItemHandle>;
     fn boxed_clone(&self) -> Box<dyn ItemHandle>;
     fn act_as_type<'a>(&'a self, type_id: TypeId, cx: &'a AppContext) -> Option<AnyView>;
+    fn serialize(&self, cx: &AppContext) -> Option<(&'static str, serde_json::Value)>;
 }
 
 pub trait WeakItemHandle: Send + Sync {
@@ -253,6 +322,14 @@ impl<T: Item> ItemHandle for View<T> {
         self.update(cx, |this, cx| this.deactivated(cx));
     }
 
+    fn set_nav_history(&self, history: ItemNavHistory, cx: &mut WindowContext) {
+        self.update(cx, |this, cx| this.set_nav_history(history, cx));
+    }
+
+    fn navigate(&self, data: &dyn Any, cx: &mut WindowContext) -> bool {
+        self.update(cx, |this, cx| this.navigate(data, cx))
+    }
+
     fn workspace_deactivated(&self, cx: &mut WindowContext) {
         self.update(cx, |this, cx| this.workspace_deactivated(cx));
     }
@@ -280,6 +357,12 @@ impl<T: Item> ItemHandle for View<T> {
     fn downgrade_item(&self) -> Box<dyn WeakItemHandle> {
         Box::new(self.downgrade())
     }
+
+    fn serialize(&self, cx: &AppContext) -> Option<(&'static str, serde_json::Value)> {
+        let kind = T::serialized_item_kind()?;
+        let state = self.read(cx).serialize(cx)?;
+        Some((kind, state))
+    }
 }
 
 impl From<Box<dyn ItemHandle>> for AnyView {
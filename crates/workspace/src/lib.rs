@@ -4,6 +4,7 @@ pub mod model_layer;
 pub mod notification;
 pub mod pane;
 pub mod pane_group;
+pub mod persistence;
 mod title_bar;
 mod util;
 mod workspace;
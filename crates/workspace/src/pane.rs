@@ -1,7 +1,7 @@
 use std::{
     any::Any,
     cmp,
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, VecDeque},
     fmt, mem,
     ops::ControlFlow,
     rc::Rc,
@@ -12,10 +12,12 @@ use anyhow::Result;
 use gpui::{
     actions, div, impl_actions, prelude::FluentBuilder as _, px, AppContext, DefiniteLength,
     DragMoveEvent, Element as _, EntityId, EventEmitter, FocusHandle, FocusOutEvent, FocusableView,
-    InteractiveElement as _, IntoElement, KeyContext, ParentElement, Pixels, Point, Render,
-    ScrollHandle, StatefulInteractiveElement, Styled, Subscription, Task, View, ViewContext,
-    VisualContext as _, WeakFocusHandle, WeakView, WindowContext,
+    InteractiveElement as _, IntoElement, KeyContext, MouseButton, NavigationDirection,
+    ParentElement, Pixels, Point, Render, ScrollHandle, StatefulInteractiveElement, Styled,
+    Subscription, Task, View, ViewContext, VisualContext as _, WeakFocusHandle, WeakView,
+    WindowContext,
 };
+use parking_lot::Mutex;
 use serde::Deserialize;
 
 use crate::util::ResultExt;
@@ -28,7 +30,7 @@ use ui::{
 };
 
 use super::{
-    item::{ItemHandle, TabContentParams},
+    item::{ItemHandle, ItemNavHistory, TabContentParams, WeakItemHandle},
     pane_group::SplitDirection,
     workspace::Workspace,
 };
@@ -64,6 +66,7 @@ pub enum Event {
     ActivateItem { local: bool },
     Remove,
     RemoveItem { item_id: EntityId },
+    CloseItem(Box<dyn ItemHandle>, usize),
     Split(SplitDirection),
     ChangeItemTitle,
     Focus,
@@ -87,6 +90,11 @@ impl fmt::Debug for Event {
                 .debug_struct("RemoveItem")
                 .field("item_id", item_id)
                 .finish(),
+            Event::CloseItem(item, ix) => f
+                .debug_tuple("CloseItem")
+                .field(&item.item_id())
+                .field(ix)
+                .finish(),
             Event::Split(direction) => f
                 .debug_struct("Split")
                 .field("direction", direction)
@@ -99,6 +107,94 @@ impl fmt::Debug for Event {
     }
 }
 
+/// The max number of entries to keep in the navigation history of a pane.
+const MAX_NAVIGATION_HISTORY_LEN: usize = 1024;
+
+struct NavigationEntry {
+    item: Arc<dyn WeakItemHandle>,
+    data: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+#[derive(Default)]
+struct NavHistoryState {
+    entries: VecDeque<NavigationEntry>,
+    /// The index of the current entry in `entries`.
+    current: usize,
+    /// Ignore the pushes while navigating, the navigation only moves the `current`.
+    navigating: bool,
+}
+
+/// The navigation history of a pane, shared with the items in the pane to push their own entries.
+#[derive(Clone, Default)]
+pub struct NavHistory(Arc<Mutex<NavHistoryState>>);
+
+impl NavHistory {
+    pub(crate) fn push(
+        &self,
+        item: Arc<dyn WeakItemHandle>,
+        data: Option<Arc<dyn Any + Send + Sync>>,
+    ) {
+        let mut state = self.0.lock();
+        if state.navigating {
+            return;
+        }
+
+        // Push a new entry will drop the forward entries.
+        let len = if state.entries.is_empty() {
+            0
+        } else {
+            state.current + 1
+        };
+        state.entries.truncate(len);
+
+        if let Some(last) = state.entries.back_mut() {
+            if last.item.id() == item.id() {
+                if data.is_none() {
+                    return;
+                }
+
+                // Replace the activation entry of the same item by the entry from the item.
+                if last.data.is_none() {
+                    last.data = data;
+                    return;
+                }
+            }
+        }
+
+        state.entries.push_back(NavigationEntry { item, data });
+        if state.entries.len() > MAX_NAVIGATION_HISTORY_LEN {
+            state.entries.pop_front();
+        }
+        state.current = state.entries.len() - 1;
+    }
+
+    /// Move to the previous or next entry, returns the item and data of the entry.
+    fn step(
+        &self,
+        mode: NavigationMode,
+    ) -> Option<(Arc<dyn WeakItemHandle>, Option<Arc<dyn Any + Send + Sync>>)> {
+        let mut state = self.0.lock();
+        let ix = match mode {
+            NavigationMode::GoingBack => state.current.checked_sub(1)?,
+            NavigationMode::GoingForward => state.current + 1,
+        };
+        let entry = state.entries.get(ix)?;
+        let result = (entry.item.clone(), entry.data.clone());
+        state.current = ix;
+        Some(result)
+    }
+
+    fn set_navigating(&self, navigating: bool) {
+        self.0.lock().navigating = navigating;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NavigationMode {
+    GoingBack,
+    GoingForward,
+}
+
 #[derive(Clone)]
 pub struct DraggedTab {
     pub pane: View<Pane>,
@@ -137,6 +233,7 @@ pub struct Pane {
     can_split: bool,
     should_display_tab_bar: Rc<dyn Fn(&ViewContext<Pane>) -> bool>,
     tab_bar_scroll_handle: ScrollHandle,
+    nav_history: NavHistory,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -171,6 +268,7 @@ impl Pane {
             can_split: true,
             should_display_tab_bar: Rc::new(|_| true),
             tab_bar_scroll_handle: ScrollHandle::new(),
+            nav_history: NavHistory::default(),
             _subscriptions: subscriptions,
         }
     }
@@ -226,6 +324,8 @@ impl Pane {
                         .iter()
                         .position(|i| i.item_id() == item.item_id())
                     {
+                        // Let the workspace remember the closed item for `ReopenClosedItem`.
+                        cx.emit(Event::CloseItem(item.boxed_clone(), item_ix));
                         pane.remove_item(item_ix, false, cx);
                     }
                 })
@@ -310,6 +410,11 @@ impl Pane {
             .iter()
             .position(|existing_item| existing_item.item_id() == item.item_id());
 
+        item.set_nav_history(
+            ItemNavHistory::new(self.nav_history.clone(), item.downgrade_item()),
+            cx,
+        );
+
         if let Some(existing_item_index) = existing_item_index {
             // If the item already exists, move it to the desired destination and activate it
 
@@ -387,6 +492,11 @@ impl Pane {
                 }
             }
 
+            if let Some(item) = self.items.get(index) {
+                self.nav_history
+                    .push(Arc::from(item.downgrade_item()), None);
+            }
+
             cx.emit(Event::ActivateItem {
                 local: activate_pane,
             });
@@ -400,6 +510,41 @@ impl Pane {
         }
     }
 
+    /// Go back to the previous entry in the navigation history.
+    pub fn navigate_backward(&mut self, cx: &mut ViewContext<Self>) {
+        self.navigate_history(NavigationMode::GoingBack, cx);
+    }
+
+    /// Go forward to the next entry in the navigation history.
+    pub fn navigate_forward(&mut self, cx: &mut ViewContext<Self>) {
+        self.navigate_history(NavigationMode::GoingForward, cx);
+    }
+
+    fn navigate_history(&mut self, mode: NavigationMode, cx: &mut ViewContext<Self>) {
+        while let Some((item, data)) = self.nav_history.step(mode) {
+            // Skip the entries of the items that have been closed or moved to other pane.
+            let Some(index) = self
+                .items
+                .iter()
+                .position(|existing_item| existing_item.item_id() == item.id())
+            else {
+                continue;
+            };
+
+            self.nav_history.set_navigating(true);
+            let mut navigated = index != self.active_item_index;
+            self.activate_item(index, true, true, cx);
+            if let Some(data) = data {
+                navigated |= self.items[index].navigate(&*data, cx);
+            }
+            self.nav_history.set_navigating(false);
+
+            if navigated {
+                break;
+            }
+        }
+    }
+
     pub fn activate_prev_item(&mut self, activate_pane: bool, cx: &mut ViewContext<Self>) {
         let mut index = self.active_item_index;
         if index > 0 {
@@ -839,9 +984,16 @@ impl Render for Pane {
                 cx.listener(|pane, _: &SplitRight, cx| pane.split(SplitDirection::Right, cx)),
             )
             .on_action(cx.listener(|pane, _: &SplitDown, cx| pane.split(SplitDirection::Down, cx)))
-            // .on_action(cx.listener(|pane, _: &GoBack, cx| pane.navigate_backward(cx)))
-            // .on_action(cx.listener(|pane, _: &GoForward, cx| pane.navigate_forward(cx)))
+            .on_action(cx.listener(|pane, _: &GoBack, cx| pane.navigate_backward(cx)))
+            .on_action(cx.listener(|pane, _: &GoForward, cx| pane.navigate_forward(cx)))
             // .on_action(cx.listener(Pane::toggle_zoom))
+            .on_action(cx.listener(|pane: &mut Pane, _: &ReopenClosedItem, cx| {
+                if let Some(workspace) = pane.workspace.upgrade() {
+                    cx.window_context().defer(move |cx| {
+                        workspace.update(cx, |workspace, cx| workspace.reopen_closed_item(cx))
+                    });
+                }
+            }))
             .on_action(cx.listener(|pane: &mut Pane, action: &ActivateItem, cx| {
                 pane.activate_item(action.0, true, true, cx);
             }))
@@ -954,32 +1106,14 @@ impl Render for Pane {
                             }),
                     )
             })
-        // .on_mouse_down(
-        //     MouseButton::Navigate(NavigationDirection::Back),
-        //     cx.listener(|pane, _, cx| {
-        //         if let Some(workspace) = pane.workspace.upgrade() {
-        //             let pane = cx.view().downgrade();
-        //             cx.window_context().defer(move |cx| {
-        //                 workspace.update(cx, |workspace, cx| {
-        //                     workspace.go_back(pane, cx).detach_and_log_err(cx)
-        //                 })
-        //             })
-        //         }
-        //     }),
-        // )
-        // .on_mouse_down(
-        //     MouseButton::Navigate(NavigationDirection::Forward),
-        //     cx.listener(|pane, _, cx| {
-        //         if let Some(workspace) = pane.workspace.upgrade() {
-        //             let pane = cx.view().downgrade();
-        //             cx.window_context().defer(move |cx| {
-        //                 workspace.update(cx, |workspace, cx| {
-        //                     workspace.go_forward(pane, cx).detach_and_log_err(cx)
-        //                 })
-        //             })
-        //         }
-        //     }),
-        // )
+            .on_mouse_down(
+                MouseButton::Navigate(NavigationDirection::Back),
+                cx.listener(|pane, _, cx| pane.navigate_backward(cx)),
+            )
+            .on_mouse_down(
+                MouseButton::Navigate(NavigationDirection::Forward),
+                cx.listener(|pane, _, cx| pane.navigate_forward(cx)),
+            )
     }
 }
 
@@ -0,0 +1,267 @@
+//! Save the workspace layout into a local file, and restore it on the next launch.
+//!
+//! The layout includes the pane split tree with flexes, the items of each pane,
+//! the active pane, and the state of the docks.
+//!
+//! Items are opt-in by implementing `Item::serialized_item_kind`, `Item::serialize` and `Item::deserialize`,
+//! and registering the item type by [`register_serializable_item`].
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{px, AppContext, Axis, Global, Task, View, WeakView, WindowContext};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dock::Dock,
+    item::{Item, ItemHandle},
+    pane::Pane,
+    pane_group::{Member, PaneAxis},
+    Workspace, WorkspaceId,
+};
+
+struct WorkspaceDb {
+    dir: PathBuf,
+}
+
+impl Global for WorkspaceDb {}
+
+/// Initialize the workspace persistence, the workspace layouts will be saved into the `dir`.
+///
+/// Only the workspace created with a `WorkspaceId` will be saved.
+pub fn init(dir: impl Into<PathBuf>, cx: &mut AppContext) {
+    cx.set_global(WorkspaceDb { dir: dir.into() });
+}
+
+type ItemDeserializer = fn(
+    WeakView<Workspace>,
+    serde_json::Value,
+    &mut WindowContext,
+) -> Task<Result<Box<dyn ItemHandle>>>;
+
+#[derive(Default)]
+struct ItemDeserializers(HashMap<&'static str, ItemDeserializer>);
+
+impl Global for ItemDeserializers {}
+
+/// Register an item type to be restored from the saved workspace layout.
+///
+/// The item must return a kind from `Item::serialized_item_kind`, otherwise it will be ignored.
+pub fn register_serializable_item<T: Item>(cx: &mut AppContext) {
+    let Some(kind) = T::serialized_item_kind() else {
+        return;
+    };
+
+    cx.default_global::<ItemDeserializers>()
+        .0
+        .insert(kind, |workspace, state, cx| {
+            let task = T::deserialize(workspace, state, cx);
+            cx.foreground_executor().spawn(async move {
+                let view = task.await?;
+                Ok(Box::new(view) as Box<dyn ItemHandle>)
+            })
+        });
+}
+
+pub(crate) fn deserialize_item(
+    item: &SerializedItem,
+    workspace: WeakView<Workspace>,
+    cx: &mut WindowContext,
+) -> Task<Result<Box<dyn ItemHandle>>> {
+    let Some(deserialize) = cx
+        .try_global::<ItemDeserializers>()
+        .and_then(|deserializers| deserializers.0.get(item.kind.as_str()).copied())
+    else {
+        return Task::ready(Err(anyhow!(
+            "No deserializer registered for item kind: {}",
+            item.kind
+        )));
+    };
+
+    deserialize(workspace, item.state.clone(), cx)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedWorkspace {
+    pub center_group: SerializedPaneGroup,
+    pub docks: DockStructure,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SerializedPaneGroup {
+    Group {
+        axis: SerializedAxis,
+        flexes: Option<Vec<f32>>,
+        children: Vec<SerializedPaneGroup>,
+    },
+    Pane(SerializedPane),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SerializedAxis {
+    Horizontal,
+    Vertical,
+}
+
+impl From<Axis> for SerializedAxis {
+    fn from(axis: Axis) -> Self {
+        match axis {
+            Axis::Horizontal => Self::Horizontal,
+            Axis::Vertical => Self::Vertical,
+        }
+    }
+}
+
+impl From<SerializedAxis> for Axis {
+    fn from(axis: SerializedAxis) -> Self {
+        match axis {
+            SerializedAxis::Horizontal => Self::Horizontal,
+            SerializedAxis::Vertical => Self::Vertical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SerializedPane {
+    /// Whether this pane is the active pane of the workspace.
+    pub active: bool,
+    pub items: Vec<SerializedItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedItem {
+    /// The kind returned by `Item::serialized_item_kind`.
+    pub kind: String,
+    /// Whether this item is the active item of the pane.
+    pub active: bool,
+    /// The state returned by `Item::serialize`.
+    pub state: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DockStructure {
+    pub left: DockData,
+    pub right: DockData,
+    pub bottom: DockData,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DockData {
+    pub visible: bool,
+    /// The `Panel::persistent_name` of the active panel.
+    pub active_panel: Option<String>,
+    /// The size of the active panel.
+    pub size: Option<f32>,
+}
+
+impl SerializedWorkspace {
+    /// Returns the path of the saved layout file, if the persistence is initialized.
+    pub(crate) fn path(workspace_id: WorkspaceId, cx: &AppContext) -> Option<PathBuf> {
+        let db = cx.try_global::<WorkspaceDb>()?;
+        Some(
+            db.dir
+                .join(format!("workspace-{}.json", i64::from(workspace_id))),
+        )
+    }
+
+    /// Read the saved layout, returns `None` if the file is not exists.
+    pub(crate) fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read workspace from {:?}", path))?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("failed to write workspace to {:?}", path))
+    }
+}
+
+impl SerializedPaneGroup {
+    pub(crate) fn serialize(member: &Member, active_pane: &View<Pane>, cx: &WindowContext) -> Self {
+        match member {
+            Member::Axis(PaneAxis {
+                axis,
+                members,
+                flexes,
+                ..
+            }) => Self::Group {
+                axis: (*axis).into(),
+                flexes: Some(flexes.lock().clone()),
+                children: members
+                    .iter()
+                    .map(|member| Self::serialize(member, active_pane, cx))
+                    .collect(),
+            },
+            Member::Pane(pane) => Self::Pane(SerializedPane::serialize(pane, active_pane, cx)),
+        }
+    }
+}
+
+impl SerializedPane {
+    fn serialize(pane: &View<Pane>, active_pane: &View<Pane>, cx: &WindowContext) -> Self {
+        let pane_ref = pane.read(cx);
+        let active_item_index = pane_ref.active_item_index();
+        let items = pane_ref
+            .items()
+            .enumerate()
+            .filter_map(|(ix, item)| {
+                let (kind, state) = item.serialize(cx)?;
+                Some(SerializedItem {
+                    kind: kind.to_string(),
+                    active: ix == active_item_index,
+                    state,
+                })
+            })
+            .collect();
+
+        Self {
+            active: pane == active_pane,
+            items,
+        }
+    }
+}
+
+impl DockData {
+    pub(crate) fn serialize(dock: &View<Dock>, cx: &WindowContext) -> Self {
+        let dock = dock.read(cx);
+        let active_panel = dock.active_panel();
+
+        Self {
+            visible: dock.is_open(),
+            active_panel: active_panel.map(|panel| panel.persistent_name().to_string()),
+            size: active_panel.map(|panel| panel.size(cx).0),
+        }
+    }
+
+    pub(crate) fn restore(&self, dock: &View<Dock>, cx: &mut WindowContext) {
+        dock.update(cx, |dock, cx| {
+            if let Some(ix) = self
+                .active_panel
+                .as_ref()
+                .and_then(|name| dock.panel_index_for_persistent_name(name))
+            {
+                dock.activate_panel(ix, cx);
+                if let Some(size) = self.size {
+                    dock.resize_active_panel(Some(px(size)), cx);
+                }
+            }
+
+            dock.set_open(self.visible, cx);
+        });
+    }
+}
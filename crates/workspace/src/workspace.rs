@@ -10,6 +10,8 @@ use crate::{
     model_layer::ModalLayer,
     notification::{NotificationHandle, NotificationId},
     pane_group,
+    persistence::{self, DockData, DockStructure, SerializedPaneGroup, SerializedWorkspace},
+    util::ResultExt as _,
 };
 use anyhow::Result;
 use gpui::{
@@ -24,8 +26,9 @@ use ui::{h_flex, theme::ActiveTheme};
 
 use super::{
     dock::{Dock, DockPosition},
+    item::{ItemHandle, WeakItemHandle},
     pane::{self, Pane},
-    pane_group::{PaneGroup, SplitDirection},
+    pane_group::{Member, PaneAxis, PaneGroup, SplitDirection},
 };
 
 actions!(
@@ -60,9 +63,43 @@ impl_actions!(
     [ActivatePane, ActivatePaneInDirection, SwapPaneInDirection,]
 );
 
+/// The max number of closed items to remember for `ReopenClosedItem`.
+const MAX_CLOSED_ITEMS: usize = 50;
+
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct WorkspaceId(i64);
 
+impl From<i64> for WorkspaceId {
+    fn from(id: i64) -> Self {
+        Self(id)
+    }
+}
+
+impl From<WorkspaceId> for i64 {
+    fn from(id: WorkspaceId) -> Self {
+        id.0
+    }
+}
+
+enum ClosedItemHandle {
+    /// The item can't be serialized, keep it alive to reopen it.
+    Item(Box<dyn ItemHandle>),
+    /// The item is not kept alive, it will be rebuilt by the serialized state once the view is released.
+    Serialized {
+        item: Box<dyn WeakItemHandle>,
+        serialized: persistence::SerializedItem,
+    },
+}
+
+struct ClosedItem {
+    item_id: EntityId,
+    item: ClosedItemHandle,
+    pane: WeakView<Pane>,
+    /// The bounds of the pane when the item was closed, used to find the nearest pane if the pane is gone.
+    pane_bounds: Option<Bounds<Pixels>>,
+    index: usize,
+}
+
 enum ActivateInDirectionTarget {
     Pane(View<Pane>),
     Dock(View<Dock>),
@@ -87,6 +124,10 @@ pub struct Workspace {
     modal_layer: View<ModalLayer>,
     workspace_actions: Vec<Box<dyn Fn(Div, &mut ViewContext<Self>) -> Div>>,
     bounds_save_task_queued: Option<Task<()>>,
+    closed_items: Vec<ClosedItem>,
+    /// Skip the serialization while the layout is restoring.
+    restoring: bool,
+    _schedule_serialize: Option<Task<()>>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -347,6 +388,9 @@ impl Workspace {
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             bounds_save_task_queued: None,
+            closed_items: Vec::new(),
+            restoring: false,
+            _schedule_serialize: None,
             _subscriptions: subscriptions,
         }
     }
@@ -432,8 +476,8 @@ impl Workspace {
             )
             .on_action(cx.listener(Workspace::activate_pane_at_index))
             .on_action(
-                cx.listener(|_workspace: &mut Workspace, _: &ReopenClosedItem, _cx| {
-                    // workspace.reopen_closed_item(cx).detach();
+                cx.listener(|workspace: &mut Workspace, _: &ReopenClosedItem, cx| {
+                    workspace.reopen_closed_item(cx);
                 }),
             )
     }
@@ -664,13 +708,105 @@ impl Workspace {
         &self.active_pane
     }
 
-    // pub fn reopen_closed_item(&mut self, cx: &mut ViewContext<Workspace>) -> Task<Result<()>> {
-    //     self.navigate_history(
-    //         self.active_pane().downgrade(),
-    //         NavigationMode::ReopeningClosedItem,
-    //         cx,
-    //     )
-    // }
+    /// Reopen the most recently closed item into its original pane and position.
+    ///
+    /// If the pane is gone, the item will be added to the nearest pane.
+    /// The released item is rebuilt by `Item::deserialize`, if that fails, the next closed item will be reopened.
+    pub fn reopen_closed_item(&mut self, cx: &mut ViewContext<Self>) {
+        while let Some(closed_item) = self.closed_items.pop() {
+            // Skip the item that has been opened again, e.g. moved to other pane.
+            if self.panes_by_item.contains_key(&closed_item.item_id) {
+                continue;
+            }
+
+            let task = match closed_item.item {
+                ClosedItemHandle::Item(item) => Task::ready(Ok(item)),
+                ClosedItemHandle::Serialized { item, serialized } => match item.upgrade() {
+                    Some(item) => Task::ready(Ok(item)),
+                    None => persistence::deserialize_item(&serialized, self.weak_handle(), cx),
+                },
+            };
+
+            let pane = closed_item
+                .pane
+                .upgrade()
+                .filter(|pane| self.panes.contains(pane))
+                .unwrap_or_else(|| self.nearest_pane(closed_item.pane_bounds))
+                .downgrade();
+            let index = closed_item.index;
+
+            cx.spawn(|this, mut cx| async move {
+                let item = match task.await {
+                    Ok(item) => item,
+                    Err(err) => {
+                        this.update(&mut cx, |this, cx| this.reopen_closed_item(cx))?;
+                        return Err(err);
+                    }
+                };
+                pane.update(&mut cx, |pane, cx| {
+                    pane.add_item(item, true, true, Some(index), cx);
+                })
+            })
+            .detach_and_log_err(cx);
+            return;
+        }
+    }
+
+    fn push_closed_item(
+        &mut self,
+        pane: &View<Pane>,
+        item: &dyn ItemHandle,
+        index: usize,
+        cx: &AppContext,
+    ) {
+        if self.closed_items.len() >= MAX_CLOSED_ITEMS {
+            self.closed_items.remove(0);
+        }
+
+        let handle = match item.serialize(cx) {
+            Some((kind, state)) => ClosedItemHandle::Serialized {
+                item: item.downgrade_item(),
+                serialized: persistence::SerializedItem {
+                    kind: kind.to_string(),
+                    active: true,
+                    state,
+                },
+            },
+            None => ClosedItemHandle::Item(item.boxed_clone()),
+        };
+
+        self.closed_items.push(ClosedItem {
+            item_id: item.item_id(),
+            item: handle,
+            pane: pane.downgrade(),
+            pane_bounds: self.center.bounding_box_for_pane(pane),
+            index,
+        });
+    }
+
+    /// Returns the pane that nearest to the given bounds, fallback to the active pane.
+    fn nearest_pane(&self, bounds: Option<Bounds<Pixels>>) -> View<Pane> {
+        let Some(bounds) = bounds else {
+            return self.active_pane.clone();
+        };
+
+        let center = bounds.center();
+        if let Some(pane) = self.center.pane_at_pixel_position(center) {
+            return pane.clone();
+        }
+
+        self.center
+            .panes()
+            .into_iter()
+            .filter_map(|pane| {
+                let pane_bounds = self.center.bounding_box_for_pane(pane)?;
+                let offset = pane_bounds.center() - center;
+                Some((pane, offset.x.0.powi(2) + offset.y.0.powi(2)))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(pane, _)| pane.clone())
+            .unwrap_or_else(|| self.active_pane.clone())
+    }
 
     fn activate_pane_at_index(&mut self, action: &ActivatePane, cx: &mut ViewContext<Self>) {
         let panes = self.center.panes();
@@ -868,6 +1004,9 @@ impl Workspace {
                 item.added_to_pane(self, pane, cx);
                 cx.emit(Event::ItemAdded);
             }
+            pane::Event::CloseItem(item, ix) => {
+                self.push_closed_item(&pane, item.as_ref(), *ix, cx);
+            }
             pane::Event::Split(direction) => {
                 self.split_and_clone(pane, *direction, cx);
             }
@@ -1006,19 +1145,200 @@ impl Workspace {
         cx.notify();
     }
 
-    pub(crate) fn serialize_workspace(&mut self, _cx: &mut ViewContext<Self>) {
-        // if self._schedule_serialize.is_none() {
-        //     self._schedule_serialize = Some(cx.spawn(|this, mut cx| async move {
-        //         cx.background_executor()
-        //             .timer(Duration::from_millis(100))
-        //             .await;
-        //         this.update(&mut cx, |this, cx| {
-        //             this.serialize_workspace_internal(cx).detach();
-        //             this._schedule_serialize.take();
-        //         })
-        //         .log_err();
-        //     }));
-        // }
+    pub(crate) fn serialize_workspace(&mut self, cx: &mut ViewContext<Self>) {
+        if self.database_id.is_none() || self.restoring {
+            return;
+        }
+
+        if self._schedule_serialize.is_none() {
+            self._schedule_serialize = Some(cx.spawn(|this, mut cx| async move {
+                cx.background_executor()
+                    .timer(Duration::from_millis(100))
+                    .await;
+                this.update(&mut cx, |this, cx| {
+                    this.serialize_workspace_internal(cx).detach_and_log_err(cx);
+                    this._schedule_serialize.take();
+                })
+                .log_err();
+            }));
+        }
+    }
+
+    fn serialize_workspace_internal(&self, cx: &mut WindowContext) -> Task<Result<()>> {
+        let Some(path) = self
+            .database_id
+            .and_then(|workspace_id| SerializedWorkspace::path(workspace_id, cx))
+        else {
+            return Task::ready(Ok(()));
+        };
+
+        let serialized = SerializedWorkspace {
+            center_group: SerializedPaneGroup::serialize(&self.center.root, &self.active_pane, cx),
+            docks: DockStructure {
+                left: DockData::serialize(&self.left_dock, cx),
+                right: DockData::serialize(&self.right_dock, cx),
+                bottom: DockData::serialize(&self.bottom_dock, cx),
+            },
+        };
+
+        cx.background_executor()
+            .spawn(async move { serialized.write(&path) })
+    }
+
+    /// Restore the workspace layout that saved with the `WorkspaceId` of this workspace.
+    ///
+    /// Returns `false` if there is no saved layout or none of the items can be restored, then the caller can build the default layout.
+    ///
+    /// The saved layout is kept on the disk until the restore is succeeded.
+    pub fn restore(&mut self, cx: &mut ViewContext<Self>) -> Task<Result<bool>> {
+        let Some(path) = self
+            .database_id
+            .and_then(|workspace_id| SerializedWorkspace::path(workspace_id, cx))
+        else {
+            return Task::ready(Ok(false));
+        };
+
+        self.restoring = true;
+        cx.spawn(|this, mut cx| async move {
+            let result = async {
+                let Some(serialized) = cx
+                    .background_executor()
+                    .spawn(async move { SerializedWorkspace::read(&path) })
+                    .await?
+                else {
+                    return Ok(false);
+                };
+
+                let item_tasks =
+                    this.update(&mut cx, |this, cx| this.load_serialized(serialized, cx))?;
+
+                let mut restored_count = 0;
+                for (pane, items) in item_tasks {
+                    let mut active_ix = None;
+                    for (active, task) in items {
+                        let Some(item) = task.await.log_err() else {
+                            continue;
+                        };
+
+                        pane.update(&mut cx, |pane, cx| {
+                            let ix = pane.items_len();
+                            pane.add_item(item, false, false, Some(ix), cx);
+                            if active {
+                                active_ix = Some(ix);
+                            }
+                        })?;
+                        restored_count += 1;
+                    }
+
+                    if let Some(ix) = active_ix {
+                        pane.update(&mut cx, |pane, cx| pane.activate_item(ix, false, false, cx))?;
+                    }
+                }
+
+                anyhow::Ok(restored_count > 0)
+            }
+            .await;
+
+            this.update(&mut cx, |this, cx| {
+                this.restoring = false;
+                // Don't overwrite the saved layout by a failed restore, e.g.: the items are unknown.
+                if matches!(result, Ok(true)) {
+                    this.serialize_workspace(cx);
+                }
+            })?;
+
+            result
+        })
+    }
+
+    /// Replace the panes by the serialized layout, returns the tasks to deserialize the items of each pane.
+    fn load_serialized(
+        &mut self,
+        serialized: SerializedWorkspace,
+        cx: &mut ViewContext<Self>,
+    ) -> Vec<(View<Pane>, Vec<(bool, Task<Result<Box<dyn ItemHandle>>>)>)> {
+        let old_panes = std::mem::take(&mut self.panes);
+        for pane in &old_panes {
+            for item in pane.read(cx).items() {
+                self.panes_by_item.remove(&item.item_id());
+            }
+        }
+
+        let mut active_pane = None;
+        let mut pane_items = vec![];
+        let root = self.load_pane_group(
+            serialized.center_group,
+            &mut active_pane,
+            &mut pane_items,
+            cx,
+        );
+        self.center = PaneGroup { root };
+
+        let active_pane = active_pane.unwrap_or_else(|| self.panes[0].clone());
+        self.active_pane = active_pane.clone();
+        self.last_active_center_pane = Some(active_pane.downgrade());
+        cx.focus_view(&active_pane);
+
+        serialized.docks.left.restore(&self.left_dock, cx);
+        serialized.docks.right.restore(&self.right_dock, cx);
+        serialized.docks.bottom.restore(&self.bottom_dock, cx);
+
+        let workspace = self.weak_handle();
+        let item_tasks = pane_items
+            .into_iter()
+            .map(|(pane, items)| {
+                let tasks = items
+                    .iter()
+                    .map(|item| {
+                        (
+                            item.active,
+                            persistence::deserialize_item(item, workspace.clone(), cx),
+                        )
+                    })
+                    .collect();
+                (pane, tasks)
+            })
+            .collect();
+
+        cx.notify();
+        item_tasks
+    }
+
+    fn load_pane_group(
+        &mut self,
+        group: SerializedPaneGroup,
+        active_pane: &mut Option<View<Pane>>,
+        pane_items: &mut Vec<(View<Pane>, Vec<persistence::SerializedItem>)>,
+        cx: &mut ViewContext<Self>,
+    ) -> Member {
+        match group {
+            SerializedPaneGroup::Group {
+                axis,
+                flexes,
+                children,
+            } if !children.is_empty() => {
+                let mut members = children
+                    .into_iter()
+                    .map(|child| self.load_pane_group(child, active_pane, pane_items, cx))
+                    .collect::<Vec<_>>();
+
+                if members.len() == 1 {
+                    return members.remove(0);
+                }
+
+                let flexes = flexes.filter(|flexes| flexes.len() == members.len());
+                Member::Axis(PaneAxis::load(axis.into(), members, flexes))
+            }
+            SerializedPaneGroup::Group { .. } => Member::Pane(self.add_pane(cx)),
+            SerializedPaneGroup::Pane(serialized_pane) => {
+                let pane = self.add_pane(cx);
+                if serialized_pane.active {
+                    *active_pane = Some(pane.clone());
+                }
+                pane_items.push((pane.clone(), serialized_pane.items));
+                Member::Pane(pane)
+            }
+        }
     }
 
     fn render_notifications(&self, _cx: &ViewContext<Self>) -> Option<Div> {